@@ -8,6 +8,18 @@ use std::fs;
 struct RazCalConfig {
     /// The microcontroller RazCAL is being built for.
     mcu: String,
+
+    /// Allows the LFXT crystal pins (PJ0 and PJ1) to be released for GPIO use.
+    #[serde(default)]
+    release_lfxt_pins: bool,
+
+    /// Allows the HFXT crystal pins (PJ2 and PJ3) to be released for GPIO use.
+    #[serde(default)]
+    release_hfxt_pins: bool,
+
+    /// Allows the JTAG pins (PJ4 and PJ5) to be released for GPIO use.
+    #[serde(default)]
+    release_jtag_pins: bool,
 }
 
 /// Supported MSP432 Package Types
@@ -46,6 +58,23 @@ fn main() {
         }
     }
 
+    // Reserved pins that the configuration allows to be used as GPIO.
+    println!("cargo:rustc-check-cfg=cfg(razcal_release_lfxt_pins)");
+    println!("cargo:rustc-check-cfg=cfg(razcal_release_hfxt_pins)");
+    println!("cargo:rustc-check-cfg=cfg(razcal_release_jtag_pins)");
+
+    if config.release_lfxt_pins {
+        println!("cargo:rustc-cfg=razcal_release_lfxt_pins");
+    }
+
+    if config.release_hfxt_pins {
+        println!("cargo:rustc-cfg=razcal_release_hfxt_pins");
+    }
+
+    if config.release_jtag_pins {
+        println!("cargo:rustc-cfg=razcal_release_jtag_pins");
+    }
+
     // MSP432 Support 8-bit and 16-bit GPIO ports.
     println!("cargo:rustc-cfg=razcal_gpio_port_size=\"{}\"", 8);
     println!("cargo:rustc-cfg=razcal_gpio_port_size=\"{}\"", 16);
@@ -0,0 +1,322 @@
+//! # Clock
+//! The `clock` module includes structures and functions to configure the Clock System (CS), such
+//! as the external crystal oscillators.
+
+//
+// Dependencies
+//
+
+use crate::{
+    pin::{AlternatePin, Pin, PinMode, ReservedPin},
+    registers::{ReadOnly, ReadWrite, Reserved, WriteOnly, PERIPHERAL_BASE},
+};
+
+//
+// Consts
+//
+
+/// Base address of the Clock System module.
+const CS_MODULE: u32 = PERIPHERAL_BASE + 0x1_0400;
+
+/// Key that unlocks the Clock System registers.
+const CSKEY_UNLOCK: u32 = 0x695A;
+
+/// Any value other than the unlock key locks the Clock System registers.
+const CSKEY_LOCK: u32 = 0;
+
+const LFXTDRIVE_SHIFT: u8 = 0;
+const LFXTDRIVE_MASK: u32 = 0x3 << LFXTDRIVE_SHIFT;
+const LFXT_EN_MASK: u32 = 1 << 8;
+const LFXTBYPASS_MASK: u32 = 1 << 9;
+
+const HFXTDRIVE_MASK: u32 = 1 << 16;
+const HFXTFREQ_SHIFT: u8 = 20;
+const HFXTFREQ_MASK: u32 = 0x7 << HFXTFREQ_SHIFT;
+const HFXT_EN_MASK: u32 = 1 << 24;
+const HFXTBYPASS_MASK: u32 = 1 << 25;
+
+const LFXTIFG_MASK: u32 = 1 << 0;
+const HFXTIFG_MASK: u32 = 1 << 1;
+
+/// Nominal frequency of the LFXT crystal.
+pub const LFXT_FREQUENCY_HZ: u32 = 32_768;
+
+//
+// Structures
+//
+
+#[repr(C)]
+/// Clock System Register layout
+struct ClockSystem {
+    /// Unlocks the other Clock System registers when written with the unlock key.
+    key: ReadWrite<u32>,
+
+    /// DCO frequency configuration.
+    control_0: ReadWrite<u32>,
+
+    /// Clock source and divider selection.
+    control_1: ReadWrite<u32>,
+
+    /// Crystal oscillator configuration.
+    control_2: ReadWrite<u32>,
+
+    /// Start counter configuration.
+    control_3: ReadWrite<u32>,
+
+    /// Unused.
+    reserved: [Reserved<u32>; 7],
+
+    /// Clock enables for conditional clock requests.
+    clock_enable: ReadWrite<u32>,
+
+    /// Status of the clocks.
+    status: ReadOnly<u32>,
+
+    /// Unused.
+    reserved2: [Reserved<u32>; 2],
+
+    /// Enables the fault interrupts.
+    interrupt_enable: ReadWrite<u32>,
+
+    /// Unused.
+    reserved3: Reserved<u32>,
+
+    /// Indicates the oscillator faults.
+    interrupt_flag: ReadOnly<u32>,
+
+    /// Unused.
+    reserved4: Reserved<u32>,
+
+    /// Clears the oscillator faults when a 1 is written.
+    clear_interrupt_flag: WriteOnly<u32>,
+}
+
+/// Represents the frequencies of the clocks feeding the peripherals, as configured by the
+/// application. Peripheral drivers use them to derive their bit rates.
+#[derive(Clone, Copy, Debug)]
+pub struct ClockFrequencies {
+    /// Frequency of the auxiliary clock (ACLK), in Hz.
+    pub aclk_hz: u32,
+
+    /// Frequency of the subsystem master clock (SMCLK), in Hz.
+    pub smclk_hz: u32,
+}
+
+impl Default for ClockFrequencies {
+    /// Gets the clock frequencies after a reset, with ACLK sourced from REFOCLK and SMCLK sourced
+    /// from the DCO.
+    fn default() -> Self {
+        ClockFrequencies {
+            aclk_hz: 32_768,
+            smclk_hz: 3_000_000,
+        }
+    }
+}
+
+/// Represents the drive strength of the LFXT oscillator. Higher drive strengths start up faster
+/// and tolerate higher load capacitances at the cost of power consumption.
+#[derive(Clone, Copy)]
+pub enum LfxtDrive {
+    Lowest = 0,
+    Low = 1,
+    High = 2,
+    Highest = 3,
+}
+
+/// Represents the low frequency crystal oscillator (LFXT), driven through PJ0 (LFXIN) and PJ1
+/// (LFXOUT).
+pub struct Lfxt {
+    /// The LFXIN pin.
+    xin: AlternatePin<Pin<'J', 0>>,
+
+    /// The LFXOUT pin.
+    xout: AlternatePin<Pin<'J', 1>>,
+}
+
+impl Lfxt {
+    /// Starts the low frequency crystal oscillator.
+    ///
+    /// # Arguments
+    /// `xin` - Provides the LFXIN pin.
+    /// `xout` - Provides the LFXOUT pin.
+    /// `drive` - Provides the drive strength of the oscillator.
+    ///
+    /// # Returns
+    /// The running oscillator. Should be checked with `is_stable` before being relied upon.
+    pub fn start(xin: ReservedPin<'J', 0>, xout: ReservedPin<'J', 1>, drive: LfxtDrive) -> Self {
+        let lfxt = Lfxt {
+            xin: AlternatePin::new(xin.into_pin(), PinMode::Alternate1),
+            xout: AlternatePin::new(xout.into_pin(), PinMode::Alternate1),
+        };
+
+        modify_clock_system(|cs| {
+            cs.control_2.modify(|value| {
+                (value & !(LFXTDRIVE_MASK | LFXTBYPASS_MASK))
+                    | ((drive as u32) << LFXTDRIVE_SHIFT)
+                    | LFXT_EN_MASK
+            });
+        });
+
+        lfxt
+    }
+
+    /// Checks if the oscillator is running without faults. A detected fault is cleared, so the
+    /// oscillator is only stable once this returns `true`.
+    ///
+    /// # Returns
+    /// `true` if no fault was detected since the last check.
+    /// `false` otherwise.
+    pub fn is_stable(&self) -> bool {
+        check_oscillator_fault(LFXTIFG_MASK)
+    }
+
+    /// Stops the oscillator.
+    ///
+    /// # Returns
+    /// The LFXIN and LFXOUT pins.
+    pub fn stop(self) -> (ReservedPin<'J', 0>, ReservedPin<'J', 1>) {
+        modify_clock_system(|cs| cs.control_2.clear_bits(LFXT_EN_MASK));
+
+        (
+            ReservedPin::from_pin(self.xin.into_pin()),
+            ReservedPin::from_pin(self.xout.into_pin()),
+        )
+    }
+}
+
+/// Represents the high frequency crystal oscillator (HFXT), driven through PJ3 (HFXIN) and PJ2
+/// (HFXOUT).
+pub struct Hfxt {
+    /// The HFXOUT pin.
+    xout: AlternatePin<Pin<'J', 2>>,
+
+    /// The HFXIN pin.
+    xin: AlternatePin<Pin<'J', 3>>,
+
+    /// The frequency of the crystal.
+    frequency_hz: u32,
+}
+
+impl Hfxt {
+    /// Starts the high frequency crystal oscillator.
+    ///
+    /// # Arguments
+    /// `xout` - Provides the HFXOUT pin.
+    /// `xin` - Provides the HFXIN pin.
+    /// `frequency_hz` - Provides the frequency of the crystal, between 1 MHz and 48 MHz.
+    ///
+    /// # Returns
+    /// The running oscillator. Should be checked with `is_stable` before being relied upon.
+    pub fn start(xout: ReservedPin<'J', 2>, xin: ReservedPin<'J', 3>, frequency_hz: u32) -> Self {
+        debug_assert!((1_000_000..=48_000_000).contains(&frequency_hz));
+
+        let hfxt = Hfxt {
+            xout: AlternatePin::new(xout.into_pin(), PinMode::Alternate1),
+            xin: AlternatePin::new(xin.into_pin(), PinMode::Alternate1),
+            frequency_hz,
+        };
+
+        let frequency_range: u32 = match frequency_hz {
+            0..=4_000_000 => 0,
+            4_000_001..=8_000_000 => 1,
+            8_000_001..=16_000_000 => 2,
+            16_000_001..=24_000_000 => 3,
+            24_000_001..=32_000_000 => 4,
+            32_000_001..=40_000_000 => 5,
+            _ => 6,
+        };
+
+        // The higher drive strength is required above 4 MHz.
+
+        let drive = if frequency_range == 0 {
+            0
+        } else {
+            HFXTDRIVE_MASK
+        };
+
+        modify_clock_system(|cs| {
+            cs.control_2.modify(|value| {
+                (value & !(HFXTFREQ_MASK | HFXTDRIVE_MASK | HFXTBYPASS_MASK))
+                    | (frequency_range << HFXTFREQ_SHIFT)
+                    | drive
+                    | HFXT_EN_MASK
+            });
+        });
+
+        hfxt
+    }
+
+    /// Gets the frequency of the crystal.
+    ///
+    /// # Returns
+    /// Frequency in Hz.
+    pub fn get_frequency(&self) -> u32 {
+        self.frequency_hz
+    }
+
+    /// Checks if the oscillator is running without faults. A detected fault is cleared, so the
+    /// oscillator is only stable once this returns `true`.
+    ///
+    /// # Returns
+    /// `true` if no fault was detected since the last check.
+    /// `false` otherwise.
+    pub fn is_stable(&self) -> bool {
+        check_oscillator_fault(HFXTIFG_MASK)
+    }
+
+    /// Stops the oscillator.
+    ///
+    /// # Returns
+    /// The HFXOUT and HFXIN pins.
+    pub fn stop(self) -> (ReservedPin<'J', 2>, ReservedPin<'J', 3>) {
+        modify_clock_system(|cs| cs.control_2.clear_bits(HFXT_EN_MASK));
+
+        (
+            ReservedPin::from_pin(self.xout.into_pin()),
+            ReservedPin::from_pin(self.xin.into_pin()),
+        )
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Gets the Clock System registers.
+///
+/// # Returns
+/// The Clock System registers.
+fn get_clock_system() -> &'static ClockSystem {
+    unsafe { &*(CS_MODULE as *const ClockSystem) }
+}
+
+/// Modifies the Clock System registers, unlocking them for the duration of the modification.
+///
+/// # Arguments
+/// `modify_func` - A function to modify the registers.
+fn modify_clock_system<F: FnOnce(&ClockSystem)>(modify_func: F) {
+    let cs = get_clock_system();
+
+    cs.key.write(CSKEY_UNLOCK);
+    modify_func(cs);
+    cs.key.write(CSKEY_LOCK);
+}
+
+/// Checks an oscillator fault flag, clearing it if set.
+///
+/// # Arguments
+/// `fault_mask` - Provides the fault flag of the oscillator.
+///
+/// # Returns
+/// `true` if the fault flag was clear.
+/// `false` otherwise.
+fn check_oscillator_fault(fault_mask: u32) -> bool {
+    let cs = get_clock_system();
+
+    if (cs.interrupt_flag.read() & fault_mask) == 0 {
+        return true;
+    }
+
+    modify_clock_system(|cs| cs.clear_interrupt_flag.write(fault_mask));
+    false
+}
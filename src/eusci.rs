@@ -0,0 +1,339 @@
+//! # eUSCI
+//! The `eusci` module includes structures and functions shared by the drivers of the Enhanced
+//! Universal Serial Communication Interface (eUSCI) modules.
+
+//
+// Dependencies
+//
+
+use crate::{
+    clock::ClockFrequencies,
+    interrupt::single_proc_critical_section,
+    registers::{ReadOnly, ReadWrite, Reserved, PERIPHERAL_BASE},
+};
+use core::ptr::{addr_of, addr_of_mut};
+
+//
+// Consts
+//
+
+const EUSCI_A0_MODULE: u32 = PERIPHERAL_BASE + 0x1000;
+const EUSCI_A1_MODULE: u32 = PERIPHERAL_BASE + 0x1400;
+const EUSCI_A2_MODULE: u32 = PERIPHERAL_BASE + 0x1800;
+const EUSCI_A3_MODULE: u32 = PERIPHERAL_BASE + 0x1C00;
+const EUSCI_B0_MODULE: u32 = PERIPHERAL_BASE + 0x2000;
+const EUSCI_B1_MODULE: u32 = PERIPHERAL_BASE + 0x2400;
+const EUSCI_B2_MODULE: u32 = PERIPHERAL_BASE + 0x2800;
+const EUSCI_B3_MODULE: u32 = PERIPHERAL_BASE + 0x2C00;
+
+pub(crate) const UCSWRST_MASK: u16 = 1 << 0;
+pub(crate) const UCSSEL_SHIFT: u16 = 6;
+pub(crate) const UCSSEL_MASK: u16 = 0x3 << UCSSEL_SHIFT;
+pub(crate) const UCSYNC_MASK: u16 = 1 << 8;
+
+pub(crate) const UCRXIE_MASK: u16 = 1 << 0;
+pub(crate) const UCTXIE_MASK: u16 = 1 << 1;
+
+pub(crate) const UCRXIFG_MASK: u16 = 1 << 0;
+pub(crate) const UCTXIFG_MASK: u16 = 1 << 1;
+
+pub(crate) const UCBUSY_MASK: u16 = 1 << 0;
+pub(crate) const UCOE_MASK: u16 = 1 << 5;
+
+/// Number of eUSCI modules.
+pub(crate) const EUSCI_MODULE_COUNT: usize = 8;
+
+//
+// Traits
+//
+
+/// An eUSCI module.
+pub trait EusciInstance: private::EusciRegisterAccess {}
+
+/// An eUSCI_A module, supporting the UART and SPI modes.
+pub trait EusciAInstance: EusciInstance {}
+
+/// An eUSCI_B module, supporting the SPI and I2C modes.
+pub trait EusciBInstance: EusciInstance {}
+
+//
+// Globals
+//
+
+/// Service routines of the drivers handling the interrupts of each eUSCI module.
+static mut EUSCI_INTERRUPT_HANDLERS: [Option<fn()>; EUSCI_MODULE_COUNT] =
+    [None; EUSCI_MODULE_COUNT];
+
+//
+// Structures
+//
+
+#[repr(C)]
+/// eUSCI_A Register layout
+pub(crate) struct EusciARegisters {
+    pub(crate) control_0: ReadWrite<u16>,
+    pub(crate) control_1: ReadWrite<u16>,
+    reserved: Reserved<u16>,
+    pub(crate) baud_rate: ReadWrite<u16>,
+    pub(crate) modulation_control: ReadWrite<u16>,
+    pub(crate) status: ReadWrite<u16>,
+    pub(crate) receive_buffer: ReadOnly<u16>,
+    pub(crate) transmit_buffer: ReadWrite<u16>,
+    pub(crate) auto_baud_control: ReadWrite<u16>,
+    pub(crate) irda_control: ReadWrite<u16>,
+    reserved2: [Reserved<u16>; 3],
+    pub(crate) interrupt_enable: ReadWrite<u16>,
+    pub(crate) interrupt_flag: ReadWrite<u16>,
+    pub(crate) interrupt_vector: ReadOnly<u16>,
+}
+
+#[repr(C)]
+/// eUSCI_B Register layout
+pub(crate) struct EusciBRegisters {
+    pub(crate) control_0: ReadWrite<u16>,
+    pub(crate) control_1: ReadWrite<u16>,
+    reserved: Reserved<u16>,
+    pub(crate) baud_rate: ReadWrite<u16>,
+    pub(crate) status: ReadWrite<u16>,
+    pub(crate) byte_counter_threshold: ReadWrite<u16>,
+    pub(crate) receive_buffer: ReadOnly<u16>,
+    pub(crate) transmit_buffer: ReadWrite<u16>,
+    reserved2: [Reserved<u16>; 2],
+    pub(crate) own_address: [ReadWrite<u16>; 4],
+    pub(crate) received_address: ReadOnly<u16>,
+    pub(crate) address_mask: ReadWrite<u16>,
+    pub(crate) slave_address: ReadWrite<u16>,
+    reserved3: [Reserved<u16>; 4],
+    pub(crate) interrupt_enable: ReadWrite<u16>,
+    pub(crate) interrupt_flag: ReadWrite<u16>,
+    pub(crate) interrupt_vector: ReadOnly<u16>,
+}
+
+/// The registers found in both eUSCI_A and eUSCI_B modules, which are at different offsets.
+pub struct EusciCommonRegisters {
+    pub(crate) control_0: &'static ReadWrite<u16>,
+    pub(crate) baud_rate: &'static ReadWrite<u16>,
+    pub(crate) status: &'static ReadWrite<u16>,
+    pub(crate) receive_buffer: &'static ReadOnly<u16>,
+    pub(crate) transmit_buffer: &'static ReadWrite<u16>,
+    pub(crate) interrupt_enable: &'static ReadWrite<u16>,
+    pub(crate) interrupt_flag: &'static ReadWrite<u16>,
+    pub(crate) interrupt_vector: &'static ReadOnly<u16>,
+}
+
+/// Represents the clock feeding an eUSCI module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EusciClockSource {
+    /// The auxiliary clock (ACLK).
+    Aclk = 1,
+
+    /// The subsystem master clock (SMCLK).
+    Smclk = 2,
+}
+
+impl EusciClockSource {
+    /// Gets the frequency of the clock.
+    ///
+    /// # Arguments
+    /// `clocks` - Provides the clock frequencies.
+    ///
+    /// # Returns
+    /// Frequency in Hz.
+    pub fn get_frequency(&self, clocks: &ClockFrequencies) -> u32 {
+        match self {
+            EusciClockSource::Aclk => clocks.aclk_hz,
+            EusciClockSource::Smclk => clocks.smclk_hz,
+        }
+    }
+
+    /// Gets the UCSSEL bits selecting the clock.
+    ///
+    /// # Returns
+    /// The UCSSEL bits, in place.
+    pub(crate) fn get_select_bits(&self) -> u16 {
+        (*self as u16) << UCSSEL_SHIFT
+    }
+}
+
+macro_rules! define_eusci_instances {
+    ($($name:ident, $kind:ident, $registers:ident, $address:expr, $index:expr;)+) => {
+        $(
+            /// A zero-sized typestate indicating an eUSCI module.
+            pub struct $name;
+            impl EusciInstance for $name {}
+            impl $kind for $name {}
+            impl private::Sealed for $name {}
+
+            impl private::EusciRegisterAccess for $name {
+                fn get_common_registers() -> EusciCommonRegisters {
+                    let regs = unsafe { &*($address as *const $registers) };
+
+                    EusciCommonRegisters {
+                        control_0: &regs.control_0,
+                        baud_rate: &regs.baud_rate,
+                        status: &regs.status,
+                        receive_buffer: &regs.receive_buffer,
+                        transmit_buffer: &regs.transmit_buffer,
+                        interrupt_enable: &regs.interrupt_enable,
+                        interrupt_flag: &regs.interrupt_flag,
+                        interrupt_vector: &regs.interrupt_vector,
+                    }
+                }
+
+                fn get_address() -> u32 {
+                    $address
+                }
+
+                fn get_index() -> usize {
+                    $index
+                }
+            }
+        )+
+    };
+}
+
+define_eusci_instances!(
+    EusciA0, EusciAInstance, EusciARegisters, EUSCI_A0_MODULE, 0;
+    EusciA1, EusciAInstance, EusciARegisters, EUSCI_A1_MODULE, 1;
+    EusciA2, EusciAInstance, EusciARegisters, EUSCI_A2_MODULE, 2;
+    EusciA3, EusciAInstance, EusciARegisters, EUSCI_A3_MODULE, 3;
+    EusciB0, EusciBInstance, EusciBRegisters, EUSCI_B0_MODULE, 4;
+    EusciB1, EusciBInstance, EusciBRegisters, EUSCI_B1_MODULE, 5;
+    EusciB2, EusciBInstance, EusciBRegisters, EUSCI_B2_MODULE, 6;
+    EusciB3, EusciBInstance, EusciBRegisters, EUSCI_B3_MODULE, 7;
+);
+
+//
+// Public functions.
+//
+
+/// Services the pending interrupts of an eUSCI module by invoking the service routine of the
+/// driver using it. Should be called from the interrupt service routine of the module.
+pub fn dispatch_eusci_interrupt<Instance: EusciInstance>() {
+    let handler = unsafe { (*addr_of!(EUSCI_INTERRUPT_HANDLERS))[Instance::get_index()] };
+
+    if let Some(handler) = handler {
+        handler();
+    }
+}
+
+//
+// Crate functions
+//
+
+/// Registers the service routine invoked by `dispatch_eusci_interrupt` for an eUSCI module.
+///
+/// # Arguments
+/// `handler` - Provides the service routine, or `None` to unregister the current one.
+pub(crate) fn set_eusci_interrupt_handler<Instance: EusciInstance>(handler: Option<fn()>) {
+    single_proc_critical_section(|_| unsafe {
+        (*addr_of_mut!(EUSCI_INTERRUPT_HANDLERS))[Instance::get_index()] = handler;
+    });
+}
+
+/// Gets the registers shared by all eUSCI modules.
+///
+/// # Returns
+/// The common registers of the eUSCI module.
+pub(crate) fn get_eusci_registers<Instance: EusciInstance>() -> EusciCommonRegisters {
+    Instance::get_common_registers()
+}
+
+/// Gets the index of an eUSCI module, for drivers keeping per-module state.
+///
+/// # Returns
+/// The index of the module, below the number of eUSCI modules.
+pub(crate) fn get_eusci_index<Instance: EusciInstance>() -> usize {
+    Instance::get_index()
+}
+
+/// Gets the registers of an eUSCI_A module.
+///
+/// # Returns
+/// The registers of the eUSCI_A module.
+pub(crate) fn get_eusci_a_registers<Instance: EusciAInstance>() -> &'static EusciARegisters {
+    unsafe { &*(Instance::get_address() as *const EusciARegisters) }
+}
+
+/// Gets the registers of an eUSCI_B module.
+///
+/// # Returns
+/// The registers of the eUSCI_B module.
+pub(crate) fn get_eusci_b_registers<Instance: EusciBInstance>() -> &'static EusciBRegisters {
+    unsafe { &*(Instance::get_address() as *const EusciBRegisters) }
+}
+
+/// Modifies the configuration of an eUSCI module, holding it in reset for the duration of the
+/// modification. Every interrupt enable and flag is cleared by the reset.
+///
+/// # Arguments
+/// `configure_func` - A function to modify the registers.
+pub(crate) fn configure_eusci<Instance: EusciInstance, F: FnOnce(&EusciCommonRegisters)>(
+    configure_func: F,
+) {
+    let regs = get_eusci_registers::<Instance>();
+
+    regs.control_0.set_bits(UCSWRST_MASK);
+    configure_func(&regs);
+    regs.control_0.clear_bits(UCSWRST_MASK);
+}
+
+/// Selects the clock and prescaler producing the bit rate closest to a requested bit rate,
+/// without exceeding it when possible.
+///
+/// # Arguments
+/// `clocks` - Provides the clock frequencies.
+/// `bit_rate_hz` - Provides the requested bit rate.
+///
+/// # Returns
+/// The clock source, the prescaler and the resulting bit rate.
+pub(crate) fn select_prescaled_clock(
+    clocks: &ClockFrequencies,
+    bit_rate_hz: u32,
+) -> (EusciClockSource, u16, u32) {
+    debug_assert!(bit_rate_hz > 0);
+
+    let mut best = None;
+
+    for source in [EusciClockSource::Smclk, EusciClockSource::Aclk] {
+        let frequency = source.get_frequency(clocks);
+        if frequency == 0 {
+            continue;
+        }
+
+        let prescaler = frequency.div_ceil(bit_rate_hz).clamp(1, u16::MAX as u32);
+        let actual_hz = frequency / prescaler;
+        let error = actual_hz.abs_diff(bit_rate_hz);
+
+        match best {
+            Some((_, _, _, best_error)) if best_error <= error => (),
+            _ => best = Some((source, prescaler as u16, actual_hz, error)),
+        }
+    }
+
+    match best {
+        Some((source, prescaler, actual_hz, _)) => (source, prescaler, actual_hz),
+        None => (EusciClockSource::Smclk, 1, clocks.smclk_hz),
+    }
+}
+
+//
+// For sealed traits.
+//
+
+mod private {
+    use super::EusciCommonRegisters;
+
+    pub trait Sealed {}
+
+    pub trait EusciRegisterAccess: Sealed {
+        /// Gets the registers shared by all eUSCI modules.
+        fn get_common_registers() -> EusciCommonRegisters;
+
+        /// Gets the base address of the module.
+        fn get_address() -> u32;
+
+        /// Gets the index of the module within the tables of the drivers.
+        fn get_index() -> usize;
+    }
+}
@@ -1,14 +1,6 @@
 //! # Bus
 //! The `bus` module includes structures and function to utilize GPIO as groups of pins.
 
-//
-// TODO: Interrupts for Inputs
-//
-
-//
-// TODO: Drive strength for Outputs
-//
-
 //
 // Internal Modules
 //
@@ -23,6 +15,40 @@ mod sectionbus;
 pub use portbus::*;
 pub use sectionbus::*;
 
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{get_gpio_port, set_change_detection, set_pin_interrupt_handler, take_edges},
+    interrupt::single_proc_critical_section,
+    Edge,
+};
+use core::sync::atomic::{compiler_fence, Ordering};
+
+//
+// Structures
+//
+
+/// Describes the transitions detected on a GPIO Bus.
+pub struct GpioBusChanges {
+    /// The bits of the bus that had a low to high transition.
+    pub rising: usize,
+
+    /// The bits of the bus that had a high to low transition.
+    pub falling: usize,
+}
+
+impl GpioBusChanges {
+    /// Gets the bits of the bus that had a transition in either direction.
+    ///
+    /// # Returns
+    /// The bits that changed.
+    pub fn changed(&self) -> usize {
+        self.rising | self.falling
+    }
+}
+
 //
 // Traits
 //
@@ -63,6 +89,163 @@ pub trait GpioBusOutput<const SIZE: usize>: private::Sealed {
     fn toggle_bits(&mut self, toggle_mask: usize);
 }
 
+/// A GPIO Bus instance that is configured as an input capable of generating interrupts.
+///
+/// Interrupts are serviced by `dispatch_port_interrupt`, which must be called from the
+/// interrupt service routine of each port the bus belongs to.
+pub trait GpioBusInterrupt<const SIZE: usize>: private::Sealed {
+    /// Enables the interrupts for every bit of the GPIO Bus. Any interrupt left pending from a
+    /// previous configuration is cleared.
+    ///
+    /// # Arguments
+    /// `edge` - Provides the signal transition that raises the interrupts.
+    fn enable_interrupts(&mut self, edge: Edge);
+
+    /// Enables the interrupts for every bit of the GPIO Bus, raising them on transitions in both
+    /// directions. The detected transitions are retrieved through `take_changes`.
+    fn enable_change_detection(&mut self);
+
+    /// Disables the interrupts for every bit of the GPIO Bus.
+    fn disable_interrupts(&mut self);
+
+    /// Gets the bits of the GPIO Bus with a pending interrupt.
+    ///
+    /// # Returns
+    /// The pending bits.
+    fn get_pending(&self) -> usize;
+
+    /// Clears pending interrupts on the GPIO Bus.
+    ///
+    /// # Arguments
+    /// `clear_mask` - The bits to clear the pending interrupts for.
+    fn clear_pending(&mut self, clear_mask: usize);
+
+    /// Gets the transitions dispatched on the GPIO Bus since they were last taken, and clears
+    /// them. This is safe to call from an interrupt service routine.
+    ///
+    /// # Returns
+    /// The transitions detected for each bit of the bus.
+    fn take_changes(&self) -> GpioBusChanges;
+
+    /// Registers the handler invoked by `dispatch_port_interrupt` when any bit of the GPIO Bus
+    /// raises an interrupt.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function to invoke.
+    fn set_interrupt_handler(&mut self, handler: fn());
+
+    /// Unregisters the handler of the GPIO Bus.
+    fn clear_interrupt_handler(&mut self);
+}
+
+//
+// Module private functions.
+//
+
+/// Enables interrupts on given pins of a port.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to enable interrupts for.
+/// `edge` - Provides the signal transition that raises the interrupts.
+fn enable_bus_interrupts(port_name: char, mask: u16, edge: Edge) {
+    let port_regs = get_gpio_port(port_name);
+
+    // Changing the edge select may set the interrupt flags, so only re-enable the interrupts once
+    // the flags have been cleared. The registers are shared with the interrupt dispatcher.
+
+    single_proc_critical_section(|_| {
+        port_regs.interrupt_enable.clear_bits(mask);
+        set_change_detection(port_name, mask, false);
+
+        compiler_fence(Ordering::Release);
+
+        match edge {
+            Edge::RisingEdge => port_regs.interrupt_edge_select.clear_bits(mask),
+            Edge::FallingEdge => port_regs.interrupt_edge_select.set_bits(mask),
+        }
+
+        port_regs.interrupt_flag.clear_bits(mask);
+        take_edges(port_name, mask);
+
+        compiler_fence(Ordering::Release);
+
+        port_regs.interrupt_enable.set_bits(mask);
+    });
+}
+
+/// Enables interrupts on given pins of a port for transitions in both directions.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to enable interrupts for.
+fn enable_bus_change_detection(port_name: char, mask: u16) {
+    let port_regs = get_gpio_port(port_name);
+
+    single_proc_critical_section(|_| {
+        port_regs.interrupt_enable.clear_bits(mask);
+
+        compiler_fence(Ordering::Release);
+
+        // Wait for the transition away from the current level of each pin. The dispatcher
+        // flips the edge select every time a transition is detected.
+
+        let level = port_regs.input.read();
+        port_regs
+            .interrupt_edge_select
+            .modify(|value| (value & !mask) | (level & mask));
+
+        port_regs.interrupt_flag.clear_bits(mask);
+        take_edges(port_name, mask);
+        set_change_detection(port_name, mask, true);
+
+        compiler_fence(Ordering::Release);
+
+        port_regs.interrupt_enable.set_bits(mask);
+    });
+}
+
+/// Disables interrupts on given pins of a port.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to disable interrupts for.
+fn disable_bus_interrupts(port_name: char, mask: u16) {
+    let port_regs = get_gpio_port(port_name);
+
+    single_proc_critical_section(|_| {
+        port_regs.interrupt_enable.clear_bits(mask);
+        set_change_detection(port_name, mask, false);
+    });
+}
+
+/// Clears pending interrupts on given pins of a port.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to clear the pending interrupts for.
+fn clear_bus_pending(port_name: char, mask: u16) {
+    let port_regs = get_gpio_port(port_name);
+
+    single_proc_critical_section(|_| {
+        port_regs.interrupt_flag.clear_bits(mask);
+    });
+}
+
+/// Registers the interrupt handler for given pins of a port.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to register the handler for.
+/// `handler` - Provides the handler to register, or `None` to unregister the current handler.
+fn set_bus_interrupt_handler(port_name: char, mask: u16, handler: Option<fn()>) {
+    for offset in 0..16 {
+        if (mask & (1 << offset)) != 0 {
+            set_pin_interrupt_handler(port_name, offset, handler);
+        }
+    }
+}
+
 //
 // For sealed traits.
 //
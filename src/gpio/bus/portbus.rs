@@ -7,13 +7,25 @@
 
 use crate::{
     gpio::{
-        get_gpio_port, Disabled, GpioIn, GpioInputMode, GpioMode, GpioOut, GpioOutputMode,
-        HighImpedance, OpenCollector, PullDown, PullUp, PushPull,
+        declare_safe_state, get_gpio_port, release_to_safe_state, Disabled, GpioIn, GpioInputMode,
+        GpioMode, GpioOut, GpioOutputMode, HighImpedance, OpenCollector, PullDown, PullUp,
+        PushPull, SafeState,
     },
-    pin::PortX,
+    pin::{claim_pins, release_pins, PortWithInterrupt, PortX},
+    Edge,
 };
 
-use super::{private, GpioBusInput, GpioBusOutput};
+use core::{
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use super::{
+    clear_bus_pending, disable_bus_interrupts, enable_bus_change_detection, enable_bus_interrupts,
+    private, set_bus_interrupt_handler, take_edges, GpioBusChanges, GpioBusInput, GpioBusInterrupt,
+    GpioBusOutput,
+};
 
 //
 // Constants
@@ -51,7 +63,7 @@ impl<Port: PortX, Mode: GpioMode> GpioPortBus<Port, Mode> {
                 _input_mode: HighImpedance,
             },
 
-            port: self.port,
+            port: self.into_port(),
         }
     }
 
@@ -71,7 +83,7 @@ impl<Port: PortX, Mode: GpioMode> GpioPortBus<Port, Mode> {
                 _input_mode: PullUp,
             },
 
-            port: self.port,
+            port: self.into_port(),
         }
     }
 
@@ -91,7 +103,7 @@ impl<Port: PortX, Mode: GpioMode> GpioPortBus<Port, Mode> {
                 _input_mode: PullDown,
             },
 
-            port: self.port,
+            port: self.into_port(),
         }
     }
 
@@ -103,6 +115,7 @@ impl<Port: PortX, Mode: GpioMode> GpioPortBus<Port, Mode> {
         let port_regs = get_gpio_port(self.port.get_port_name());
 
         port_regs.output.write(0);
+        port_regs.drive_strength.write(0);
         port_regs.direction.write(ALL_PINS_MASK);
 
         GpioPortBus {
@@ -110,7 +123,7 @@ impl<Port: PortX, Mode: GpioMode> GpioPortBus<Port, Mode> {
                 _output_mode: PushPull,
             },
 
-            port: self.port,
+            port: self.into_port(),
         }
     }
 
@@ -122,6 +135,7 @@ impl<Port: PortX, Mode: GpioMode> GpioPortBus<Port, Mode> {
         let port_regs = get_gpio_port(self.port.get_port_name());
 
         port_regs.output.write(0);
+        port_regs.drive_strength.write(0);
         port_regs.direction.write(ALL_PINS_MASK);
         port_regs.resistor_enable.write(ALL_PINS_MASK);
 
@@ -130,9 +144,56 @@ impl<Port: PortX, Mode: GpioMode> GpioPortBus<Port, Mode> {
                 _output_mode: OpenCollector,
             },
 
-            port: self.port,
+            port: self.into_port(),
         }
     }
+
+    /// Declares the state bits of the GPIO Bus are placed in when the bus is dropped, extracted,
+    /// or when `enter_safe_state` is called.
+    ///
+    /// # Arguments
+    /// `mask` - The bits to declare the safe state for.
+    /// `state` - Provides the safe state of the bits.
+    pub fn set_safe_state(&mut self, mask: usize, state: SafeState) {
+        declare_safe_state(self.port.get_port_name(), mask as u16, Some(state));
+    }
+
+    /// Removes the declared safe state of bits of the GPIO Bus.
+    ///
+    /// # Arguments
+    /// `mask` - The bits to remove the safe state for.
+    pub fn clear_safe_state(&mut self, mask: usize) {
+        declare_safe_state(self.port.get_port_name(), mask as u16, None);
+    }
+
+    /// Break down the GPIO Bus back to its original Port structure. Each bit is placed in its
+    /// declared safe state, or in high-impedance input mode if none was declared.
+    ///
+    /// # Returns
+    /// The Port structure contained by the GPIO Bus.
+    pub fn extract_port(self) -> Port {
+        let bus_mask = ALL_PINS_MASK;
+        let released_mask = release_to_safe_state(self.port.get_port_name(), bus_mask);
+        let highz_mask = bus_mask & !released_mask;
+
+        let port_regs = get_gpio_port(self.port.get_port_name());
+        port_regs.resistor_enable.clear_bits(highz_mask);
+        port_regs.direction.clear_bits(highz_mask);
+
+        release_pins(self.port.get_port_name(), bus_mask);
+        self.into_port()
+    }
+
+    /// Moves the port out of this instance without placing it in its safe state.
+    ///
+    /// # Returns
+    /// The Port structure contained by the GPIO Bus.
+    fn into_port(self) -> Port {
+        let gpio_bus = ManuallyDrop::new(self);
+
+        // SAFE: The instance is never dropped, so the port is only owned by the caller.
+        unsafe { ptr::read(&gpio_bus.port) }
+    }
 }
 
 impl<Port: PortX, InputMode: GpioInputMode> GpioBusInput<16>
@@ -161,6 +222,75 @@ impl<Port: PortX, OutputMode: GpioOutputMode> GpioBusInput<16>
     }
 }
 
+impl<Port: PortWithInterrupt, InputMode: GpioInputMode> GpioBusInterrupt<16>
+    for GpioPortBus<Port, GpioIn<InputMode>>
+{
+    /// Enables the interrupts for every bit of the GPIO Bus. Any interrupt left pending from a
+    /// previous configuration is cleared.
+    ///
+    /// # Arguments
+    /// `edge` - Provides the signal transition that raises the interrupts.
+    fn enable_interrupts(&mut self, edge: Edge) {
+        enable_bus_interrupts(self.port.get_port_name(), ALL_PINS_MASK, edge);
+    }
+
+    /// Enables the interrupts for every bit of the GPIO Bus, raising them on transitions in both
+    /// directions. The detected transitions are retrieved through `take_changes`.
+    fn enable_change_detection(&mut self) {
+        enable_bus_change_detection(self.port.get_port_name(), ALL_PINS_MASK);
+    }
+
+    /// Disables the interrupts for every bit of the GPIO Bus.
+    fn disable_interrupts(&mut self) {
+        disable_bus_interrupts(self.port.get_port_name(), ALL_PINS_MASK);
+    }
+
+    /// Gets the bits of the GPIO Bus with a pending interrupt.
+    ///
+    /// # Returns
+    /// The pending bits.
+    fn get_pending(&self) -> usize {
+        let port_regs = get_gpio_port(self.port.get_port_name());
+        port_regs.interrupt_flag.read() as usize
+    }
+
+    /// Clears pending interrupts on the GPIO Bus.
+    ///
+    /// # Arguments
+    /// `clear_mask` - The bits to clear the pending interrupts for.
+    fn clear_pending(&mut self, clear_mask: usize) {
+        clear_bus_pending(self.port.get_port_name(), clear_mask as u16);
+    }
+
+    /// Gets the transitions dispatched on the GPIO Bus since they were last taken, and clears
+    /// them. This is safe to call from an interrupt service routine.
+    ///
+    /// # Returns
+    /// The transitions detected for each bit of the bus.
+    fn take_changes(&self) -> GpioBusChanges {
+        let (rising, falling) = take_edges(self.port.get_port_name(), ALL_PINS_MASK);
+
+        GpioBusChanges {
+            rising: rising as usize,
+            falling: falling as usize,
+        }
+    }
+
+    /// Registers the handler invoked by `dispatch_port_interrupt` when any bit of the GPIO Bus
+    /// raises an interrupt.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function to invoke.
+    fn set_interrupt_handler(&mut self, handler: fn()) {
+        set_bus_interrupt_handler(self.port.get_port_name(), ALL_PINS_MASK, Some(handler));
+    }
+
+    /// Unregisters the handler of the GPIO Bus.
+    fn clear_interrupt_handler(&mut self) {
+        set_bus_interrupt_handler(self.port.get_port_name(), ALL_PINS_MASK, None);
+    }
+}
+
 impl<Port: PortX> GpioBusOutput<16> for GpioPortBus<Port, GpioOut<PushPull>> {
     /// Sets the value of the GPIO Bus.
     ///
@@ -200,10 +330,40 @@ impl<Port: PortX> GpioBusOutput<16> for GpioPortBus<Port, GpioOut<PushPull>> {
 }
 
 //
-// Note: GpioPortBus<Port, GpioOut<OpenCollector>> is not implemented as the output value cannot
-// be changed atomically.
+// Note: GpioPortBus<Port, GpioOut<OpenCollector>> does not implement GpioBusOutput, as the
+// output value cannot be changed atomically. Bits are instead released and driven low by separate
+// calls, each a non-atomic read-modify-write of the direction and output registers.
 //
 
+/// The following implements output control for GPIO Port Bus in open collector configuration.
+impl<Port: PortX> GpioPortBus<Port, GpioOut<OpenCollector>> {
+    /// Releases bits on the GPIO Bus, letting the pull-up resistors or the bus pull them high.
+    ///
+    /// # Arguments
+    /// `release_mask` - The bits to release.
+    pub fn release_bits(&mut self, release_mask: usize) {
+        let port_regs = get_gpio_port(self.port.get_port_name());
+        port_regs.direction.clear_bits(release_mask as u16);
+
+        compiler_fence(Ordering::Release);
+
+        port_regs.output.set_bits(release_mask as u16);
+    }
+
+    /// Drives bits on the GPIO Bus low.
+    ///
+    /// # Arguments
+    /// `low_mask` - The bits to drive low.
+    pub fn drive_bits_low(&mut self, low_mask: usize) {
+        let port_regs = get_gpio_port(self.port.get_port_name());
+        port_regs.output.clear_bits(low_mask as u16);
+
+        compiler_fence(Ordering::Release);
+
+        port_regs.direction.set_bits(low_mask as u16);
+    }
+}
+
 impl<Port: PortX> GpioPortBus<Port, Disabled> {
     /// Allocates a new GPIO configured Port.
     ///
@@ -213,6 +373,8 @@ impl<Port: PortX> GpioPortBus<Port, Disabled> {
     /// # Returns
     /// A GPIO Port in the `Disabled` configuration.
     pub fn new(port: Port) -> Self {
+        claim_pins(port.get_port_name(), ALL_PINS_MASK);
+
         Self {
             _config: Disabled,
             port: port,
@@ -220,4 +382,11 @@ impl<Port: PortX> GpioPortBus<Port, Disabled> {
     }
 }
 
+impl<Port: PortX, Mode: GpioMode> Drop for GpioPortBus<Port, Mode> {
+    /// Places the bits of the GPIO Bus in their declared safe state, if any.
+    fn drop(&mut self) {
+        release_to_safe_state(self.port.get_port_name(), ALL_PINS_MASK);
+    }
+}
+
 impl<Port: PortX, Mode: GpioMode> private::Sealed for GpioPortBus<Port, Mode> {}
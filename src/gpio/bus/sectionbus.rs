@@ -4,13 +4,27 @@
 
 use crate::{
     gpio::{
-        get_gpio_port, Disabled, GpioIn, GpioInputMode, GpioMode, GpioOut, GpioOutputMode,
-        HighImpedance, OpenCollector, PullDown, PullUp, PushPull,
+        declare_safe_state, get_gpio_port, release_to_safe_state, Disabled, GpioIn, GpioInputMode,
+        GpioMode, GpioOut, GpioOutputMode, GpioPushPullMode, HighDrive, HighImpedance,
+        OpenCollector, PullDown, PullUp, PushPull, SafeState,
     },
-    pin::PortSectionX,
+    pin::{
+        claim_pins, release_pins, PortSectionWithHighDrive, PortSectionWithInterrupt, PortSectionX,
+    },
+    Edge,
+};
+
+use core::{
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
 };
 
-use super::{private, GpioBusInput, GpioBusOutput};
+use super::{
+    clear_bus_pending, disable_bus_interrupts, enable_bus_change_detection, enable_bus_interrupts,
+    private, set_bus_interrupt_handler, take_edges, GpioBusChanges, GpioBusInput, GpioBusInterrupt,
+    GpioBusOutput,
+};
 
 //
 // Structures.
@@ -49,7 +63,7 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode>
                 _input_mode: HighImpedance,
             },
 
-            section: self.section,
+            section: self.into_section(),
         }
     }
 
@@ -75,7 +89,7 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode>
                 _input_mode: PullUp,
             },
 
-            section: self.section,
+            section: self.into_section(),
         }
     }
 
@@ -101,7 +115,7 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode>
                 _input_mode: PullDown,
             },
 
-            section: self.section,
+            section: self.into_section(),
         }
     }
 
@@ -113,6 +127,9 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode>
         let port_regs = get_gpio_port(self.section.get_port_name());
 
         port_regs.output.clear_bits(self.section.get_mask() as u16);
+        port_regs
+            .drive_strength
+            .clear_bits(self.section.get_mask() as u16);
         port_regs.direction.set_bits(self.section.get_mask() as u16);
 
         GpioSectionBus {
@@ -120,7 +137,7 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode>
                 _output_mode: PushPull,
             },
 
-            section: self.section,
+            section: self.into_section(),
         }
     }
 
@@ -132,6 +149,9 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode>
         let port_regs = get_gpio_port(self.section.get_port_name());
 
         port_regs.output.clear_bits(self.section.get_mask() as u16);
+        port_regs
+            .drive_strength
+            .clear_bits(self.section.get_mask() as u16);
         port_regs.direction.set_bits(self.section.get_mask() as u16);
         port_regs
             .resistor_enable
@@ -142,7 +162,92 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode>
                 _output_mode: OpenCollector,
             },
 
-            section: self.section,
+            section: self.into_section(),
+        }
+    }
+
+    /// Declares the state bits of the GPIO Bus are placed in when the bus is dropped, extracted,
+    /// or when `enter_safe_state` is called.
+    ///
+    /// # Arguments
+    /// `mask` - The bits to declare the safe state for.
+    /// `state` - Provides the safe state of the bits.
+    pub fn set_safe_state(&mut self, mask: usize, state: SafeState) {
+        declare_safe_state(
+            self.section.get_port_name(),
+            ((mask << self.section.get_offset()) & self.section.get_mask()) as u16,
+            Some(state),
+        );
+    }
+
+    /// Removes the declared safe state of bits of the GPIO Bus.
+    ///
+    /// # Arguments
+    /// `mask` - The bits to remove the safe state for.
+    pub fn clear_safe_state(&mut self, mask: usize) {
+        declare_safe_state(
+            self.section.get_port_name(),
+            ((mask << self.section.get_offset()) & self.section.get_mask()) as u16,
+            None,
+        );
+    }
+
+    /// Break down the GPIO Bus back to its original Section structure. Each bit is placed in its
+    /// declared safe state, or in high-impedance input mode if none was declared.
+    ///
+    /// # Returns
+    /// The Section structure contained by the GPIO Bus.
+    pub fn extract_section(self) -> Section {
+        let bus_mask = self.section.get_mask() as u16;
+        let released_mask = release_to_safe_state(self.section.get_port_name(), bus_mask);
+        let highz_mask = bus_mask & !released_mask;
+
+        let port_regs = get_gpio_port(self.section.get_port_name());
+        port_regs.resistor_enable.clear_bits(highz_mask);
+        port_regs.direction.clear_bits(highz_mask);
+
+        release_pins(self.section.get_port_name(), bus_mask);
+        self.into_section()
+    }
+
+    /// Moves the port section out of this instance without placing it in its safe state.
+    ///
+    /// # Returns
+    /// The Section structure contained by the GPIO Bus.
+    fn into_section(self) -> Section {
+        let gpio_bus = ManuallyDrop::new(self);
+
+        // SAFE: The instance is never dropped, so the port section is only owned by the caller.
+        unsafe { ptr::read(&gpio_bus.section) }
+    }
+}
+
+/// The following implements state modification for GPIO Section Buses that support high drive
+/// strength.
+impl<const SIZE: usize, Section: PortSectionWithHighDrive<SIZE>, Mode: GpioMode>
+    GpioSectionBus<SIZE, Section, Mode>
+{
+    /// Convert this port section into an output bus with high drive strength push-pull
+    /// configuration.
+    ///
+    /// # Returns
+    /// A GPIO Section Bus instance configured in output mode with high drive strength push-pull
+    /// configuration.
+    pub fn to_output_highdrive(self) -> GpioSectionBus<SIZE, Section, GpioOut<HighDrive>> {
+        let port_regs = get_gpio_port(self.section.get_port_name());
+
+        port_regs.output.clear_bits(self.section.get_mask() as u16);
+        port_regs
+            .drive_strength
+            .set_bits(self.section.get_mask() as u16);
+        port_regs.direction.set_bits(self.section.get_mask() as u16);
+
+        GpioSectionBus {
+            _config: GpioOut {
+                _output_mode: HighDrive,
+            },
+
+            section: self.into_section(),
         }
     }
 }
@@ -175,8 +280,94 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>, OutputMode: GpioOutputMode>
     }
 }
 
-impl<const SIZE: usize, Section: PortSectionX<SIZE>> GpioBusOutput<SIZE>
-    for GpioSectionBus<SIZE, Section, GpioOut<PushPull>>
+impl<const SIZE: usize, Section: PortSectionWithInterrupt<SIZE>, InputMode: GpioInputMode>
+    GpioBusInterrupt<SIZE> for GpioSectionBus<SIZE, Section, GpioIn<InputMode>>
+{
+    /// Enables the interrupts for every bit of the GPIO Bus. Any interrupt left pending from a
+    /// previous configuration is cleared.
+    ///
+    /// # Arguments
+    /// `edge` - Provides the signal transition that raises the interrupts.
+    fn enable_interrupts(&mut self, edge: Edge) {
+        enable_bus_interrupts(
+            self.section.get_port_name(),
+            self.section.get_mask() as u16,
+            edge,
+        );
+    }
+
+    /// Enables the interrupts for every bit of the GPIO Bus, raising them on transitions in both
+    /// directions. The detected transitions are retrieved through `take_changes`.
+    fn enable_change_detection(&mut self) {
+        enable_bus_change_detection(self.section.get_port_name(), self.section.get_mask() as u16);
+    }
+
+    /// Disables the interrupts for every bit of the GPIO Bus.
+    fn disable_interrupts(&mut self) {
+        disable_bus_interrupts(self.section.get_port_name(), self.section.get_mask() as u16);
+    }
+
+    /// Gets the bits of the GPIO Bus with a pending interrupt.
+    ///
+    /// # Returns
+    /// The pending bits.
+    fn get_pending(&self) -> usize {
+        let port_regs = get_gpio_port(self.section.get_port_name());
+        ((port_regs.interrupt_flag.read() & self.section.get_mask() as u16)
+            >> self.section.get_offset()) as usize
+    }
+
+    /// Clears pending interrupts on the GPIO Bus.
+    ///
+    /// # Arguments
+    /// `clear_mask` - The bits to clear the pending interrupts for.
+    fn clear_pending(&mut self, clear_mask: usize) {
+        let masked_value =
+            ((clear_mask << self.section.get_offset()) & self.section.get_mask()) as u16;
+
+        clear_bus_pending(self.section.get_port_name(), masked_value);
+    }
+
+    /// Gets the transitions dispatched on the GPIO Bus since they were last taken, and clears
+    /// them. This is safe to call from an interrupt service routine.
+    ///
+    /// # Returns
+    /// The transitions detected for each bit of the bus.
+    fn take_changes(&self) -> GpioBusChanges {
+        let (rising, falling) =
+            take_edges(self.section.get_port_name(), self.section.get_mask() as u16);
+
+        GpioBusChanges {
+            rising: (rising >> self.section.get_offset()) as usize,
+            falling: (falling >> self.section.get_offset()) as usize,
+        }
+    }
+
+    /// Registers the handler invoked by `dispatch_port_interrupt` when any bit of the GPIO Bus
+    /// raises an interrupt.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function to invoke.
+    fn set_interrupt_handler(&mut self, handler: fn()) {
+        set_bus_interrupt_handler(
+            self.section.get_port_name(),
+            self.section.get_mask() as u16,
+            Some(handler),
+        );
+    }
+
+    /// Unregisters the handler of the GPIO Bus.
+    fn clear_interrupt_handler(&mut self) {
+        set_bus_interrupt_handler(
+            self.section.get_port_name(),
+            self.section.get_mask() as u16,
+            None,
+        );
+    }
+}
+
+impl<const SIZE: usize, Section: PortSectionX<SIZE>, OutputMode: GpioPushPullMode>
+    GpioBusOutput<SIZE> for GpioSectionBus<SIZE, Section, GpioOut<OutputMode>>
 {
     /// Sets the value of the GPIO Bus.
     ///
@@ -229,10 +420,48 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>> GpioBusOutput<SIZE>
 }
 
 //
-// Note: GpioSectionBus<Port, GpioOut<OpenCollector>> is not implemented as the output value cannot
-// be changed atomically.
+// Note: GpioSectionBus<Port, GpioOut<OpenCollector>> does not implement GpioBusOutput, as the
+// output value cannot be changed atomically. Bits are instead released and driven low by separate
+// calls, each a non-atomic read-modify-write of the direction and output registers.
 //
 
+/// The following implements output control for GPIO Section Bus in open collector configuration.
+impl<const SIZE: usize, Section: PortSectionX<SIZE>>
+    GpioSectionBus<SIZE, Section, GpioOut<OpenCollector>>
+{
+    /// Releases bits on the GPIO Bus, letting the pull-up resistors or the bus pull them high.
+    ///
+    /// # Arguments
+    /// `release_mask` - The bits to release.
+    pub fn release_bits(&mut self, release_mask: usize) {
+        let masked_value =
+            ((release_mask << self.section.get_offset()) & self.section.get_mask()) as u16;
+
+        let port_regs = get_gpio_port(self.section.get_port_name());
+        port_regs.direction.clear_bits(masked_value);
+
+        compiler_fence(Ordering::Release);
+
+        port_regs.output.set_bits(masked_value);
+    }
+
+    /// Drives bits on the GPIO Bus low.
+    ///
+    /// # Arguments
+    /// `low_mask` - The bits to drive low.
+    pub fn drive_bits_low(&mut self, low_mask: usize) {
+        let masked_value =
+            ((low_mask << self.section.get_offset()) & self.section.get_mask()) as u16;
+
+        let port_regs = get_gpio_port(self.section.get_port_name());
+        port_regs.output.clear_bits(masked_value);
+
+        compiler_fence(Ordering::Release);
+
+        port_regs.direction.set_bits(masked_value);
+    }
+}
+
 impl<const SIZE: usize, Section: PortSectionX<SIZE>> GpioSectionBus<SIZE, Section, Disabled> {
     /// Allocates a new GPIO configured Port.
     ///
@@ -242,6 +471,8 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>> GpioSectionBus<SIZE, Sectio
     /// # Returns
     /// A GPIO Port in the `Disabled` configuration.
     pub fn new(section: Section) -> Self {
+        claim_pins(section.get_port_name(), section.get_mask() as u16);
+
         Self {
             _config: Disabled,
             section: section,
@@ -249,6 +480,15 @@ impl<const SIZE: usize, Section: PortSectionX<SIZE>> GpioSectionBus<SIZE, Sectio
     }
 }
 
+impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode> Drop
+    for GpioSectionBus<SIZE, Section, Mode>
+{
+    /// Places the bits of the GPIO Bus in their declared safe state, if any.
+    fn drop(&mut self) {
+        release_to_safe_state(self.section.get_port_name(), self.section.get_mask() as u16);
+    }
+}
+
 impl<const SIZE: usize, Section: PortSectionX<SIZE>, Mode: GpioMode> private::Sealed
     for GpioSectionBus<SIZE, Section, Mode>
 {
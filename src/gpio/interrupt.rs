@@ -0,0 +1,288 @@
+//! # Interrupt
+//! The `interrupt` module includes structures and functions to dispatch GPIO port interrupts to
+//! per-pin handlers and wakers.
+
+//
+// Dependencies
+//
+
+use crate::{gpio::get_gpio_port, interrupt::single_proc_critical_section, Edge};
+use core::{
+    ptr::{addr_of, addr_of_mut},
+    sync::atomic::{compiler_fence, AtomicU16, Ordering},
+    task::Waker,
+};
+
+//
+// Structures
+//
+
+/// Represents an 8-bit GPIO port capable of generating interrupts.
+pub enum InterruptPort {
+    Port1,
+    Port2,
+    Port3,
+    Port4,
+    Port5,
+    Port6,
+}
+
+//
+// Globals
+//
+
+/// Number of 16-bit ports with interrupt capabilities (ports A, B and C).
+const INTERRUPT_PORT_COUNT: usize = 3;
+
+/// Handlers of each pin within a 16-bit port.
+type PortInterruptHandlers = [Option<fn()>; 16];
+
+/// Handlers to invoke when an interrupt is raised by a given pin.
+static mut PIN_INTERRUPT_HANDLERS: [PortInterruptHandlers; INTERRUPT_PORT_COUNT] =
+    [[None; 16]; INTERRUPT_PORT_COUNT];
+
+/// Wakers of each pin within a 16-bit port.
+type PortInterruptWakers = [Option<Waker>; 16];
+
+/// Initial value of the wakers of a 16-bit port.
+const NO_PORT_INTERRUPT_WAKERS: PortInterruptWakers = [
+    None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None,
+];
+
+/// Wakers to wake when an interrupt is raised by a given pin.
+static mut PIN_INTERRUPT_WAKERS: [PortInterruptWakers; INTERRUPT_PORT_COUNT] =
+    [NO_PORT_INTERRUPT_WAKERS; INTERRUPT_PORT_COUNT];
+
+/// Pins of each port that had a low to high transition since they were last read.
+static PORT_RISING_EDGES: [AtomicU16; INTERRUPT_PORT_COUNT] =
+    [AtomicU16::new(0), AtomicU16::new(0), AtomicU16::new(0)];
+
+/// Pins of each port that had a high to low transition since they were last read.
+static PORT_FALLING_EDGES: [AtomicU16; INTERRUPT_PORT_COUNT] =
+    [AtomicU16::new(0), AtomicU16::new(0), AtomicU16::new(0)];
+
+/// Pins of each port that detect transitions in both directions by flipping their edge select
+/// whenever an interrupt is raised.
+static PORT_CHANGE_DETECTION: [AtomicU16; INTERRUPT_PORT_COUNT] =
+    [AtomicU16::new(0), AtomicU16::new(0), AtomicU16::new(0)];
+
+//
+// Public functions.
+//
+
+/// Services all the pending interrupts of an 8-bit GPIO port by invoking the handler registered
+/// for each pin with a pending interrupt. Should be called from the interrupt service routine of
+/// the provided port.
+///
+/// # Arguments
+/// `port` - Provides the 8-bit port that raised the interrupt.
+pub fn dispatch_port_interrupt(port: InterruptPort) {
+    let (port_name, pin_base) = match port {
+        InterruptPort::Port1 => ('A', 0),
+        InterruptPort::Port2 => ('A', 8),
+        InterruptPort::Port3 => ('B', 0),
+        InterruptPort::Port4 => ('B', 8),
+        InterruptPort::Port5 => ('C', 0),
+        InterruptPort::Port6 => ('C', 8),
+    };
+
+    let port_index = get_interrupt_port_index(port_name);
+    let port_regs = get_gpio_port(port_name);
+    let interrupt_vector = if pin_base == 0 {
+        &port_regs.interrupt_vector_low
+    } else {
+        &port_regs.interrupt_vector_high
+    };
+
+    // Reading the interrupt vector clears the flag of the highest priority pending interrupt, so
+    // keep reading until no interrupts are pending.
+
+    loop {
+        let vector = interrupt_vector.read();
+        if vector == 0 {
+            break;
+        }
+
+        let offset = pin_base + (vector as u8 >> 1) - 1;
+        let pin_mask = 1 << offset;
+
+        let falling_edge = port_regs.interrupt_edge_select.get_bitband(offset).read();
+        if falling_edge {
+            PORT_FALLING_EDGES[port_index].fetch_or(pin_mask, Ordering::Relaxed);
+        } else {
+            PORT_RISING_EDGES[port_index].fetch_or(pin_mask, Ordering::Relaxed);
+        }
+
+        // Pins detecting changes wait for the opposite transition next. Should the pin already
+        // be back at its previous level, flipping the edge select raises the interrupt again.
+
+        if (PORT_CHANGE_DETECTION[port_index].load(Ordering::Relaxed) & pin_mask) != 0 {
+            port_regs
+                .interrupt_edge_select
+                .get_bitband(offset)
+                .write(!falling_edge);
+        }
+
+        if let Some(handler) = get_pin_interrupt_handler(port_name, offset) {
+            handler();
+        }
+
+        if let Some(waker) = take_pin_interrupt_waker(port_name, offset) {
+            waker.wake();
+        }
+    }
+}
+
+//
+// Crate functions
+//
+
+/// Registers the handler to invoke when an interrupt is raised by a given pin.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pin belongs to.
+/// `offset` - Provides the offset of the pin within its port.
+/// `handler` - Provides the handler to register, or `None` to unregister the current handler.
+pub(crate) fn set_pin_interrupt_handler(port_name: char, offset: u8, handler: Option<fn()>) {
+    let port_index = get_interrupt_port_index(port_name);
+
+    single_proc_critical_section(|_| unsafe {
+        (*addr_of_mut!(PIN_INTERRUPT_HANDLERS))[port_index][offset as usize] = handler;
+    });
+}
+
+/// Registers the waker to wake when an interrupt is raised by a given pin. The waker is woken at
+/// most once.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pin belongs to.
+/// `offset` - Provides the offset of the pin within its port.
+/// `waker` - Provides the waker to register, or `None` to unregister the current waker.
+pub(crate) fn set_pin_interrupt_waker(port_name: char, offset: u8, waker: Option<&Waker>) {
+    let port_index = get_interrupt_port_index(port_name);
+
+    single_proc_critical_section(|_| unsafe {
+        let registered = &mut (*addr_of_mut!(PIN_INTERRUPT_WAKERS))[port_index][offset as usize];
+        match waker {
+            Some(waker) => match registered {
+                Some(current) if current.will_wake(waker) => (),
+                _ => *registered = Some(waker.clone()),
+            },
+
+            None => *registered = None,
+        }
+    });
+}
+
+/// Enables or disables the interrupt of a pin, whatever function the pin is configured for. Lets
+/// peripheral drivers detect transitions on the pins they own. Any interrupt left pending from a
+/// previous configuration is cleared.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pin belongs to.
+/// `offset` - Provides the offset of the pin within its port.
+/// `edge` - Provides the signal transition that raises the interrupt, or `None` to disable it.
+pub(crate) fn set_pin_interrupt_edge(port_name: char, offset: u8, edge: Option<Edge>) {
+    let port_regs = get_gpio_port(port_name);
+
+    port_regs.interrupt_enable.get_bitband(offset).write(false);
+
+    let Some(edge) = edge else {
+        return;
+    };
+
+    compiler_fence(Ordering::Release);
+
+    port_regs
+        .interrupt_edge_select
+        .get_bitband(offset)
+        .write(matches!(edge, Edge::FallingEdge));
+
+    port_regs.interrupt_flag.get_bitband(offset).write(false);
+
+    compiler_fence(Ordering::Release);
+
+    port_regs.interrupt_enable.get_bitband(offset).write(true);
+}
+
+/// Enables or disables the detection of transitions in both directions for given pins.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to modify.
+/// `enabled` - Provides whether the pins should detect transitions in both directions.
+pub(crate) fn set_change_detection(port_name: char, mask: u16, enabled: bool) {
+    let port_index = get_interrupt_port_index(port_name);
+
+    if enabled {
+        PORT_CHANGE_DETECTION[port_index].fetch_or(mask, Ordering::Relaxed);
+    } else {
+        PORT_CHANGE_DETECTION[port_index].fetch_and(!mask, Ordering::Relaxed);
+    }
+}
+
+/// Gets and clears the transitions recorded for given pins.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to get the transitions for.
+///
+/// # Returns
+/// The pins with a low to high transition and the pins with a high to low transition.
+pub(crate) fn take_edges(port_name: char, mask: u16) -> (u16, u16) {
+    let port_index = get_interrupt_port_index(port_name);
+
+    let rising = PORT_RISING_EDGES[port_index].fetch_and(!mask, Ordering::Relaxed);
+    let falling = PORT_FALLING_EDGES[port_index].fetch_and(!mask, Ordering::Relaxed);
+    (rising & mask, falling & mask)
+}
+
+//
+// Module private functions.
+//
+
+/// Gets the handler registered for a given pin.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pin belongs to.
+/// `offset` - Provides the offset of the pin within its port.
+///
+/// # Returns
+/// `Some(handler)` if a handler is registered for the pin.
+/// `None` otherwise.
+fn get_pin_interrupt_handler(port_name: char, offset: u8) -> Option<fn()> {
+    let port_index = get_interrupt_port_index(port_name);
+    unsafe { (*addr_of!(PIN_INTERRUPT_HANDLERS))[port_index][offset as usize] }
+}
+
+/// Takes the waker registered for a given pin.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pin belongs to.
+/// `offset` - Provides the offset of the pin within its port.
+///
+/// # Returns
+/// `Some(waker)` if a waker was registered for the pin.
+/// `None` otherwise.
+fn take_pin_interrupt_waker(port_name: char, offset: u8) -> Option<Waker> {
+    let port_index = get_interrupt_port_index(port_name);
+    let mut waker = None;
+
+    single_proc_critical_section(|_| unsafe {
+        waker = (*addr_of_mut!(PIN_INTERRUPT_WAKERS))[port_index][offset as usize].take();
+    });
+
+    waker
+}
+
+/// Gets the index of a port within the interrupt handler table.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port.
+///
+/// # Returns
+/// The index of the port.
+fn get_interrupt_port_index(port_name: char) -> usize {
+    debug_assert!(('A'..='C').contains(&port_name));
+    (port_name as usize) - ('A' as usize)
+}
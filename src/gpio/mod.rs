@@ -9,14 +9,18 @@
 //
 
 mod bus;
+mod interrupt;
 mod pin;
+mod safestate;
 
 //
 // Reexports
 //
 
 pub use bus::*;
+pub use interrupt::*;
 pub use pin::*;
+pub use safestate::*;
 
 use crate::registers::{ReadOnly, ReadWrite, Reserved, PERIPHERAL_BASE};
 
@@ -64,10 +68,13 @@ impl GpioInputMode for PullUp {}
 pub struct PullDown;
 impl GpioInputMode for PullDown {}
 
+/// Represents a GPIO Output configuration mode that actively drives both logic levels.
+pub trait GpioPushPullMode: GpioOutputMode {}
+
 /// A zero-sized typestate indicating a GPIO instance output configuration.
 /// # Type Options
-/// `OutputMode` indicates the type of output configuration. Can be of type `PushPull` or
-/// `OpenCollector`.
+/// `OutputMode` indicates the type of output configuration. Can be of type `PushPull`,
+/// `HighDrive` or `OpenCollector`.
 pub struct GpioOut<OutputMode: GpioOutputMode> {
     _output_mode: OutputMode,
 }
@@ -77,11 +84,57 @@ impl<OutputMode: GpioOutputMode> GpioMode for GpioOut<OutputMode> {}
 /// A zero-sized typestate indicating a push-pull GPIO instance output configuration.
 pub struct PushPull;
 impl GpioOutputMode for PushPull {}
+impl GpioPushPullMode for PushPull {}
+
+/// A zero-sized typestate indicating a high drive strength push-pull GPIO instance output
+/// configuration. Only available for pins that support high drive strength.
+pub struct HighDrive;
+impl GpioOutputMode for HighDrive {}
+impl GpioPushPullMode for HighDrive {}
 
 /// A zero-sized typestate indicating an open collector GPIO instance output configuration.
 pub struct OpenCollector;
 impl GpioOutputMode for OpenCollector {}
 
+/// A typestate indicating a GPIO instance whose configuration is selected at runtime.
+pub struct Dynamic {
+    /// The current configuration.
+    mode: DynamicMode,
+}
+
+impl GpioMode for Dynamic {}
+
+/// Represents the configurations a GPIO instance in `Dynamic` mode can switch between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicMode {
+    InputHighImpedance,
+    InputPullUp,
+    InputPullDown,
+    OutputPushPull,
+    OutputOpenCollector,
+}
+
+impl DynamicMode {
+    /// Checks if the configuration is an output configuration.
+    ///
+    /// # Returns
+    /// `true` if the configuration is an output configuration.
+    /// `false` otherwise.
+    pub fn is_output(&self) -> bool {
+        matches!(
+            self,
+            DynamicMode::OutputPushPull | DynamicMode::OutputOpenCollector
+        )
+    }
+}
+
+/// Represents an error when accessing a GPIO instance in `Dynamic` mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicModeError {
+    /// The operation requires the GPIO instance to be in an output configuration.
+    NotAnOutput,
+}
+
 //
 // Consts
 //
@@ -127,8 +180,12 @@ struct GpioPort {
     /// The upper bit of the function select for a given pin.
     select_1: ReadWrite<u16>,
 
+    /// Indicates the highest priority pending interrupt of the lower half of the port. Reading
+    /// this register clears the corresponding interrupt flag.
+    interrupt_vector_low: ReadOnly<u16>,
+
     /// Unused.
-    reserved: (Reserved<u16>, Reserved<u16>, Reserved<u16>, Reserved<u16>),
+    reserved: (Reserved<u16>, Reserved<u16>, Reserved<u16>),
 
     /// If 1 is written, inverts both bits of the function select for a given pin.
     complement_selection: ReadWrite<u16>,
@@ -142,10 +199,43 @@ struct GpioPort {
 
     /// Indicates whether a high to low or low to high transition occured when interrupts are
     /// enabled for a given pin.
-    interrupt_flag: ReadOnly<u16>,
+    interrupt_flag: ReadWrite<u16>,
 
-    /// Unused.
-    reserved2: Reserved<u16>,
+    /// Indicates the highest priority pending interrupt of the upper half of the port. Reading
+    /// this register clears the corresponding interrupt flag.
+    interrupt_vector_high: ReadOnly<u16>,
+}
+
+//
+// Crate functions
+//
+
+/// Reads the level of a pin, for drivers that only know the location of the pin, such as from an
+/// interrupt service routine.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pin belongs to.
+/// `offset` - Provides the offset of the pin within its port.
+///
+/// # Returns
+/// `true` if the pin is high.
+/// `false` otherwise.
+pub(crate) fn read_pin_input(port_name: char, offset: u8) -> bool {
+    get_gpio_port(port_name).input.get_bitband(offset).read()
+}
+
+/// Drives the level of an output pin, for drivers that only know the location of the pin, such as
+/// from an interrupt service routine.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pin belongs to.
+/// `offset` - Provides the offset of the pin within its port.
+/// `level` - Provides the level, `true` for high.
+pub(crate) fn write_pin_output(port_name: char, offset: u8, level: bool) {
+    get_gpio_port(port_name)
+        .output
+        .get_bitband(offset)
+        .write(level);
 }
 
 //
@@ -182,6 +272,7 @@ mod private {
 }
 
 impl private::Sealed for Disabled {}
+impl private::Sealed for Dynamic {}
 impl<InputMode: GpioInputMode> private::Sealed for GpioIn<InputMode> {}
 impl<OutputMode: GpioOutputMode> private::Sealed for GpioOut<OutputMode> {}
 
@@ -190,4 +281,5 @@ impl private::Sealed for PullUp {}
 impl private::Sealed for PullDown {}
 
 impl private::Sealed for PushPull {}
+impl private::Sealed for HighDrive {}
 impl private::Sealed for OpenCollector {}
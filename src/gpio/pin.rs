@@ -1,23 +1,28 @@
 //! # Pin
 //! The `pin` module includes structures and functions to utilize GPIO as single independent pins.
 
-//
-// TODO: Interrupts for Inputs
-//
-
-//
-// TODO: Drive strength for Outputs
-//
-
 //
 // Dependencies
 //
 
 use crate::{
     gpio::*,
-    pin::{PinIdWithMode, PinMode, PinX},
+    pin::{
+        claim_pins, release_pins, PinId, PinIdWithMode, PinMode, PinWithHighDrive,
+        PinWithInterrupt, PinX,
+    },
+    Edge,
 };
-use core::sync::atomic::{compiler_fence, Ordering};
+use core::{
+    convert::Infallible,
+    future::poll_fn,
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
+    task::Poll,
+};
+use embedded_hal::digital::{ErrorKind, ErrorType, InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal_async::digital::Wait;
 
 //
 // Traits
@@ -52,7 +57,7 @@ pub trait GpioPinOutput: private::Sealed {
 /// Represents a pin configured for GPIO mode.
 /// # Type Options
 /// `GpioConfig` indicated the specific configuration mode the GPIO pin is in. Can be of type
-/// `Disabled`, `GpioInConfig`, or `GpioOutConfig`.
+/// `Disabled`, `GpioInConfig`, `GpioOutConfig`, or `Dynamic`.
 pub struct GpioPin<Pin: PinX, Mode: GpioMode> {
     /// The specfic GPIO configuration.
     _config: Mode,
@@ -61,6 +66,16 @@ pub struct GpioPin<Pin: PinX, Mode: GpioMode> {
     pin: Pin,
 }
 
+/// Represents a wait for an interrupt of a GPIO pin in progress. Disables the interrupt and
+/// unregisters the waker when dropped, including when the wait is cancelled.
+struct PinInterruptWait {
+    /// The name of the port the pin belongs to.
+    port_name: char,
+
+    /// The offset of the pin within its port.
+    offset: u8,
+}
+
 /// The following implements state modification for GPIO Pin configurations.
 impl<Pin: PinX, Mode: GpioMode> GpioPin<Pin, Mode> {
     /// Convert this instance into a high-impedance input pin.
@@ -68,24 +83,14 @@ impl<Pin: PinX, Mode: GpioMode> GpioPin<Pin, Mode> {
     /// # Returns
     /// A GPIO Pin instance configured in high-impedance input mode.
     pub fn to_input_highz(self) -> GpioPin<Pin, GpioIn<HighImpedance>> {
-        let port_regs = get_gpio_port(self.pin.get_port_name());
-
-        port_regs
-            .resistor_enable
-            .get_bitband(self.pin.get_offset())
-            .write(false);
-
-        port_regs
-            .direction
-            .get_bitband(self.pin.get_offset())
-            .write(false);
+        configure_input_highz(&self.pin);
 
         GpioPin {
             _config: GpioIn {
                 _input_mode: HighImpedance,
             },
 
-            pin: self.pin,
+            pin: self.into_pin(),
         }
     }
 
@@ -94,29 +99,14 @@ impl<Pin: PinX, Mode: GpioMode> GpioPin<Pin, Mode> {
     /// # Returns
     /// A GPIO Pin instance configured in pull-up input mode.
     pub fn to_input_pullup(self) -> GpioPin<Pin, GpioIn<PullUp>> {
-        let port_regs = get_gpio_port(self.pin.get_port_name());
-
-        port_regs
-            .resistor_enable
-            .get_bitband(self.pin.get_offset())
-            .write(true);
-
-        port_regs
-            .direction
-            .get_bitband(self.pin.get_offset())
-            .write(false);
-
-        port_regs
-            .output
-            .get_bitband(self.pin.get_offset())
-            .write(true);
+        configure_input_pullup(&self.pin);
 
         GpioPin {
             _config: GpioIn {
                 _input_mode: PullUp,
             },
 
-            pin: self.pin,
+            pin: self.into_pin(),
         }
     }
 
@@ -125,29 +115,14 @@ impl<Pin: PinX, Mode: GpioMode> GpioPin<Pin, Mode> {
     /// # Returns
     /// A GPIO Pin instance configured in pull-down input mode.
     pub fn to_input_pulldown(self) -> GpioPin<Pin, GpioIn<PullDown>> {
-        let port_regs = get_gpio_port(self.pin.get_port_name());
-
-        port_regs
-            .resistor_enable
-            .get_bitband(self.pin.get_offset())
-            .write(true);
-
-        port_regs
-            .direction
-            .get_bitband(self.pin.get_offset())
-            .write(false);
-
-        port_regs
-            .output
-            .get_bitband(self.pin.get_offset())
-            .write(false);
+        configure_input_pulldown(&self.pin);
 
         GpioPin {
             _config: GpioIn {
                 _input_mode: PullDown,
             },
 
-            pin: self.pin,
+            pin: self.into_pin(),
         }
     }
 
@@ -156,24 +131,14 @@ impl<Pin: PinX, Mode: GpioMode> GpioPin<Pin, Mode> {
     /// # Returns
     /// A GPIO Pin instance configured in push-pull output mode.
     pub fn to_output_pushpull(self) -> GpioPin<Pin, GpioOut<PushPull>> {
-        let port_regs = get_gpio_port(self.pin.get_port_name());
-
-        port_regs
-            .output
-            .get_bitband(self.pin.get_offset())
-            .write(false);
-
-        port_regs
-            .direction
-            .get_bitband(self.pin.get_offset())
-            .write(true);
+        configure_output_pushpull(&self.pin);
 
         GpioPin {
             _config: GpioOut {
                 _output_mode: PushPull,
             },
 
-            pin: self.pin,
+            pin: self.into_pin(),
         }
     }
 
@@ -182,6 +147,92 @@ impl<Pin: PinX, Mode: GpioMode> GpioPin<Pin, Mode> {
     /// # Returns
     /// A GPIO Pin instance configured in open collector output mode.
     pub fn to_output_opencollector(self) -> GpioPin<Pin, GpioOut<OpenCollector>> {
+        configure_output_opencollector(&self.pin);
+
+        GpioPin {
+            _config: GpioOut {
+                _output_mode: OpenCollector,
+            },
+
+            pin: self.into_pin(),
+        }
+    }
+
+    /// Convert this instance into a pin whose configuration is selected at runtime.
+    ///
+    /// # Arguments
+    /// `mode` - Provides the initial configuration of the pin.
+    ///
+    /// # Returns
+    /// A GPIO Pin instance in `Dynamic` mode.
+    pub fn to_dynamic(self, mode: DynamicMode) -> GpioPin<Pin, Dynamic> {
+        configure_dynamic_mode(&self.pin, mode);
+
+        GpioPin {
+            _config: Dynamic { mode },
+            pin: self.into_pin(),
+        }
+    }
+
+    /// Declares the state the GPIO Pin is placed in when it is dropped, extracted, or when
+    /// `enter_safe_state` is called.
+    ///
+    /// # Arguments
+    /// `state` - Provides the safe state of the pin.
+    pub fn set_safe_state(&mut self, state: SafeState) {
+        declare_safe_state(
+            self.pin.get_port_name(),
+            1 << self.pin.get_offset(),
+            Some(state),
+        );
+    }
+
+    /// Removes the declared safe state of the GPIO Pin.
+    pub fn clear_safe_state(&mut self) {
+        declare_safe_state(self.pin.get_port_name(), 1 << self.pin.get_offset(), None);
+    }
+
+    /// Break down the GPIO Pin back to its original Pin structure. The pin is placed in its
+    /// declared safe state, or in high-impedance input mode if none was declared.
+    ///
+    /// # Returns
+    /// The Pin structure contained by the GPIO Pin.
+    pub fn extract_pin(self) -> Pin {
+        if release_to_safe_state(self.pin.get_port_name(), 1 << self.pin.get_offset()) == 0 {
+            configure_input_highz(&self.pin);
+        }
+
+        release_pins(self.pin.get_port_name(), 1 << self.pin.get_offset());
+        self.into_pin()
+    }
+
+    /// Gets the pin, for drivers that need its location.
+    ///
+    /// # Returns
+    /// The Pin structure contained by the GPIO Pin.
+    pub(crate) fn get_pin(&self) -> &Pin {
+        &self.pin
+    }
+
+    /// Moves the pin out of this instance without placing it in its safe state.
+    ///
+    /// # Returns
+    /// The Pin structure contained by the GPIO Pin.
+    fn into_pin(self) -> Pin {
+        let gpio_pin = ManuallyDrop::new(self);
+
+        // SAFE: The instance is never dropped, so the pin is only owned by the caller.
+        unsafe { ptr::read(&gpio_pin.pin) }
+    }
+}
+
+/// The following implements state modification for GPIO Pins that support high drive strength.
+impl<Pin: PinWithHighDrive, Mode: GpioMode> GpioPin<Pin, Mode> {
+    /// Convert this instance into a output pin in high drive strength push-pull configuration.
+    ///
+    /// # Returns
+    /// A GPIO Pin instance configured in high drive strength push-pull output mode.
+    pub fn to_output_highdrive(self) -> GpioPin<Pin, GpioOut<HighDrive>> {
         let port_regs = get_gpio_port(self.pin.get_port_name());
 
         port_regs
@@ -190,31 +241,23 @@ impl<Pin: PinX, Mode: GpioMode> GpioPin<Pin, Mode> {
             .write(false);
 
         port_regs
-            .direction
+            .drive_strength
             .get_bitband(self.pin.get_offset())
             .write(true);
 
         port_regs
-            .resistor_enable
+            .direction
             .get_bitband(self.pin.get_offset())
             .write(true);
 
         GpioPin {
             _config: GpioOut {
-                _output_mode: OpenCollector,
+                _output_mode: HighDrive,
             },
 
-            pin: self.pin,
+            pin: self.into_pin(),
         }
     }
-
-    /// Break down the GPIO Pin back to its original Pin structure.
-    ///
-    /// # Returns
-    /// The Pin structure contained by the GPIO Pin.
-    pub fn extract_pin(self) -> Pin {
-        self.to_input_highz().pin
-    }
 }
 
 impl<Pin: PinX, InputMode: GpioInputMode> GpioPinInput for GpioPin<Pin, GpioIn<InputMode>> {
@@ -241,54 +284,108 @@ impl<Pin: PinX, OutputMode: GpioOutputMode> GpioPinInput for GpioPin<Pin, GpioOu
     }
 }
 
-impl<Pin: PinX> GpioPinOutput for GpioPin<Pin, GpioOut<PushPull>> {
-    /// Sets the GPIO Pin high.
-    fn set(&mut self) {
+impl<Pin: PinX> GpioPinInput for GpioPin<Pin, Dynamic> {
+    /// Reads the value of the GPIO pin. The level of the pin can be read in every configuration.
+    ///
+    /// # Returns
+    /// `true` if pin is high.
+    /// `false` if pin is low.
+    fn read(&self) -> bool {
         let port_regs = get_gpio_port(self.pin.get_port_name());
+        port_regs.input.get_bitband(self.pin.get_offset()).read()
+    }
+}
+
+/// The following implements interrupt support for GPIO input pins.
+impl<Pin: PinWithInterrupt, InputMode: GpioInputMode> GpioPin<Pin, GpioIn<InputMode>> {
+    /// Enables the interrupt for the GPIO pin. Any interrupt left pending from a previous
+    /// configuration is cleared.
+    ///
+    /// # Arguments
+    /// `edge` - Provides the signal transition that raises the interrupt.
+    pub fn enable_interrupt(&mut self, edge: Edge) {
+        let port_regs = get_gpio_port(self.pin.get_port_name());
+        let offset = self.pin.get_offset();
+
+        // Changing the edge select may set the interrupt flag, so only re-enable the interrupt
+        // once the flag has been cleared.
+
+        port_regs.interrupt_enable.get_bitband(offset).write(false);
+
+        compiler_fence(Ordering::Release);
 
         port_regs
-            .output
-            .get_bitband(self.pin.get_offset())
-            .write(true);
+            .interrupt_edge_select
+            .get_bitband(offset)
+            .write(match edge {
+                Edge::RisingEdge => false,
+                Edge::FallingEdge => true,
+            });
+
+        port_regs.interrupt_flag.get_bitband(offset).write(false);
+
+        compiler_fence(Ordering::Release);
+
+        port_regs.interrupt_enable.get_bitband(offset).write(true);
     }
 
-    /// Sets the GPIO Pin low.
-    fn clear(&mut self) {
+    /// Disables the interrupt for the GPIO pin.
+    pub fn disable_interrupt(&mut self) {
         let port_regs = get_gpio_port(self.pin.get_port_name());
 
         port_regs
-            .output
+            .interrupt_enable
             .get_bitband(self.pin.get_offset())
             .write(false);
     }
 
-    /// Toggles the GPIO Pin.
-    fn toggle(&mut self) {
+    /// Checks if the interrupt for the GPIO pin is pending.
+    ///
+    /// # Returns
+    /// `true` if the configured edge was detected since the interrupt was last cleared.
+    /// `false` otherwise.
+    pub fn is_pending(&self) -> bool {
         let port_regs = get_gpio_port(self.pin.get_port_name());
 
-        // UNSAFE! Since the value is a boolean and only the bottom bit is being toggled, it is ok.
-        // This is to avoid the compiler making a clz + lsrs for the toggle instead of just an xor.
-
-        unsafe {
-            port_regs
-                .output
-                .get_bitband(self.pin.get_offset())
-                .modify_raw(|value| value ^ 1);
-        }
+        port_regs
+            .interrupt_flag
+            .get_bitband(self.pin.get_offset())
+            .read()
     }
-}
 
-impl<Pin: PinX> GpioPinOutput for GpioPin<Pin, GpioOut<OpenCollector>> {
-    /// Sets the GPIO Pin high.
-    fn set(&mut self) {
+    /// Clears the pending interrupt for the GPIO pin.
+    pub fn clear_pending(&mut self) {
         let port_regs = get_gpio_port(self.pin.get_port_name());
 
         port_regs
-            .direction
+            .interrupt_flag
             .get_bitband(self.pin.get_offset())
             .write(false);
+    }
 
-        compiler_fence(Ordering::Release);
+    /// Registers the handler invoked by `dispatch_port_interrupt` when the GPIO pin raises an
+    /// interrupt.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function to invoke.
+    pub fn set_interrupt_handler(&mut self, handler: fn()) {
+        set_pin_interrupt_handler(
+            self.pin.get_port_name(),
+            self.pin.get_offset(),
+            Some(handler),
+        );
+    }
+
+    /// Unregisters the handler of the GPIO pin.
+    pub fn clear_interrupt_handler(&mut self) {
+        set_pin_interrupt_handler(self.pin.get_port_name(), self.pin.get_offset(), None);
+    }
+}
+
+impl<Pin: PinX, OutputMode: GpioPushPullMode> GpioPinOutput for GpioPin<Pin, GpioOut<OutputMode>> {
+    /// Sets the GPIO Pin high.
+    fn set(&mut self) {
+        let port_regs = get_gpio_port(self.pin.get_port_name());
 
         port_regs
             .output
@@ -304,13 +401,23 @@ impl<Pin: PinX> GpioPinOutput for GpioPin<Pin, GpioOut<OpenCollector>> {
             .output
             .get_bitband(self.pin.get_offset())
             .write(false);
+    }
 
-        compiler_fence(Ordering::Release);
+    /// Toggles the GPIO Pin.
+    fn toggle(&mut self) {
+        toggle_pushpull(&self.pin);
+    }
+}
 
-        port_regs
-            .direction
-            .get_bitband(self.pin.get_offset())
-            .write(true);
+impl<Pin: PinX> GpioPinOutput for GpioPin<Pin, GpioOut<OpenCollector>> {
+    /// Sets the GPIO Pin high.
+    fn set(&mut self) {
+        release_opencollector(&self.pin);
+    }
+
+    /// Sets the GPIO Pin low.
+    fn clear(&mut self) {
+        drive_opencollector_low(&self.pin);
     }
 
     /// Toggles the GPIO Pin.
@@ -323,6 +430,99 @@ impl<Pin: PinX> GpioPinOutput for GpioPin<Pin, GpioOut<OpenCollector>> {
     }
 }
 
+/// The following implements runtime configuration for GPIO Pins in `Dynamic` mode.
+impl<Pin: PinX> GpioPin<Pin, Dynamic> {
+    /// Gets the current configuration of the GPIO Pin.
+    ///
+    /// # Returns
+    /// The current configuration.
+    pub fn get_mode(&self) -> DynamicMode {
+        self._config.mode
+    }
+
+    /// Changes the configuration of the GPIO Pin. Does nothing if the pin is already in the
+    /// provided configuration.
+    ///
+    /// # Arguments
+    /// `mode` - Provides the new configuration.
+    pub fn set_mode(&mut self, mode: DynamicMode) {
+        if self._config.mode != mode {
+            configure_dynamic_mode(&self.pin, mode);
+            self._config.mode = mode;
+        }
+    }
+
+    /// Sets the GPIO Pin high.
+    ///
+    /// # Returns
+    /// `Ok(())` if the pin was set.
+    /// `Err(DynamicModeError::NotAnOutput)` if the pin is not in an output configuration.
+    pub fn set(&mut self) -> Result<(), DynamicModeError> {
+        match self._config.mode {
+            DynamicMode::OutputPushPull => {
+                let port_regs = get_gpio_port(self.pin.get_port_name());
+
+                port_regs
+                    .output
+                    .get_bitband(self.pin.get_offset())
+                    .write(true);
+            }
+
+            DynamicMode::OutputOpenCollector => release_opencollector(&self.pin),
+            _ => return Err(DynamicModeError::NotAnOutput),
+        }
+
+        Ok(())
+    }
+
+    /// Sets the GPIO Pin low.
+    ///
+    /// # Returns
+    /// `Ok(())` if the pin was cleared.
+    /// `Err(DynamicModeError::NotAnOutput)` if the pin is not in an output configuration.
+    pub fn clear(&mut self) -> Result<(), DynamicModeError> {
+        match self._config.mode {
+            DynamicMode::OutputPushPull => {
+                let port_regs = get_gpio_port(self.pin.get_port_name());
+
+                port_regs
+                    .output
+                    .get_bitband(self.pin.get_offset())
+                    .write(false);
+            }
+
+            DynamicMode::OutputOpenCollector => drive_opencollector_low(&self.pin),
+            _ => return Err(DynamicModeError::NotAnOutput),
+        }
+
+        Ok(())
+    }
+
+    /// Toggles the GPIO Pin.
+    ///
+    /// # Returns
+    /// `Ok(())` if the pin was toggled.
+    /// `Err(DynamicModeError::NotAnOutput)` if the pin is not in an output configuration.
+    pub fn toggle(&mut self) -> Result<(), DynamicModeError> {
+        match self._config.mode {
+            DynamicMode::OutputPushPull => {
+                toggle_pushpull(&self.pin);
+                Ok(())
+            }
+
+            DynamicMode::OutputOpenCollector => {
+                if self.read() {
+                    self.clear()
+                } else {
+                    self.set()
+                }
+            }
+
+            _ => Err(DynamicModeError::NotAnOutput),
+        }
+    }
+}
+
 impl<Pin: PinX> GpioPin<Pin, Disabled> {
     /// Allocates a new GPIO configured Pin.
     ///
@@ -332,6 +532,8 @@ impl<Pin: PinX> GpioPin<Pin, Disabled> {
     /// # Returns
     /// A GPIO Pin in the `Disabled` configuration.
     pub fn new(pin: Pin) -> Self {
+        claim_pins(pin.get_port_name(), 1 << pin.get_offset());
+
         Self {
             _config: Disabled,
             pin: pin,
@@ -339,6 +541,203 @@ impl<Pin: PinX> GpioPin<Pin, Disabled> {
     }
 }
 
+impl<Pin: PinX, Mode: GpioMode> Drop for GpioPin<Pin, Mode> {
+    /// Places the GPIO Pin in its declared safe state, if any.
+    fn drop(&mut self) {
+        release_to_safe_state(self.pin.get_port_name(), 1 << self.pin.get_offset());
+    }
+}
+
+/// The following implements asynchronous waiting for GPIO input pins.
+impl<Pin: PinWithInterrupt, InputMode: GpioInputMode> GpioPin<Pin, GpioIn<InputMode>> {
+    /// Waits for an interrupt of the GPIO pin to be dispatched by `dispatch_port_interrupt`.
+    ///
+    /// # Arguments
+    /// `edge` - Provides the signal transition to wait for.
+    /// `level` - Provides the level that, if read once the interrupt is enabled, indicates the
+    ///     pin already transitioned. `None` to always wait for the transition.
+    async fn wait_for_interrupt(&mut self, edge: Edge, level: Option<bool>) {
+        let port_name = self.pin.get_port_name();
+        let offset = self.pin.get_offset();
+
+        take_edges(port_name, 1 << offset);
+        self.enable_interrupt(edge);
+        let _wait = PinInterruptWait { port_name, offset };
+
+        if level != Some(self.read()) {
+            poll_fn(|context| {
+                set_pin_interrupt_waker(port_name, offset, Some(context.waker()));
+
+                if take_edges(port_name, 1 << offset) == (0, 0) {
+                    return Poll::Pending;
+                }
+
+                Poll::Ready(())
+            })
+            .await;
+        }
+    }
+}
+
+impl Drop for PinInterruptWait {
+    /// Disables the interrupt of the GPIO pin and unregisters the waker.
+    fn drop(&mut self) {
+        let port_regs = get_gpio_port(self.port_name);
+
+        port_regs
+            .interrupt_enable
+            .get_bitband(self.offset)
+            .write(false);
+        set_pin_interrupt_waker(self.port_name, self.offset, None);
+    }
+}
+
+//
+// embedded-hal implementations
+//
+
+impl<Pin: PinX, InputMode: GpioInputMode> ErrorType for GpioPin<Pin, GpioIn<InputMode>> {
+    type Error = Infallible;
+}
+
+impl<Pin: PinX, OutputMode: GpioOutputMode> ErrorType for GpioPin<Pin, GpioOut<OutputMode>> {
+    type Error = Infallible;
+}
+
+impl<Pin: PinX, Mode: GpioMode> InputPin for GpioPin<Pin, Mode>
+where
+    Self: GpioPinInput + ErrorType<Error = Infallible>,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(GpioPinInput::read(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!GpioPinInput::read(self))
+    }
+}
+
+impl<Pin: PinX, Mode: GpioMode> OutputPin for GpioPin<Pin, Mode>
+where
+    Self: GpioPinOutput + ErrorType<Error = Infallible>,
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        GpioPinOutput::clear(self);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        GpioPinOutput::set(self);
+        Ok(())
+    }
+}
+
+impl<Pin: PinX, Mode: GpioMode> StatefulOutputPin for GpioPin<Pin, Mode>
+where
+    Self: GpioPinOutput + ErrorType<Error = Infallible>,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        let port_regs = get_gpio_port(self.pin.get_port_name());
+        Ok(port_regs.output.get_bitband(self.pin.get_offset()).read())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_set_high().map(|value| !value)
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        GpioPinOutput::toggle(self);
+        Ok(())
+    }
+}
+
+impl embedded_hal::digital::Error for DynamicModeError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<Pin: PinX> ErrorType for GpioPin<Pin, Dynamic> {
+    type Error = DynamicModeError;
+}
+
+impl<Pin: PinX> InputPin for GpioPin<Pin, Dynamic> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(GpioPinInput::read(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!GpioPinInput::read(self))
+    }
+}
+
+impl<Pin: PinX> OutputPin for GpioPin<Pin, Dynamic> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.clear()
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set()
+    }
+}
+
+impl<Pin: PinX> StatefulOutputPin for GpioPin<Pin, Dynamic> {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        if !self._config.mode.is_output() {
+            return Err(DynamicModeError::NotAnOutput);
+        }
+
+        let port_regs = get_gpio_port(self.pin.get_port_name());
+        Ok(port_regs.output.get_bitband(self.pin.get_offset()).read())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_set_high().map(|value| !value)
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        GpioPin::toggle(self)
+    }
+}
+
+impl<Pin: PinWithInterrupt, InputMode: GpioInputMode> Wait for GpioPin<Pin, GpioIn<InputMode>> {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_interrupt(Edge::RisingEdge, Some(true)).await;
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_interrupt(Edge::FallingEdge, Some(false))
+            .await;
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_interrupt(Edge::RisingEdge, None).await;
+        Ok(())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_interrupt(Edge::FallingEdge, None).await;
+        Ok(())
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        // Only a single edge can be detected at a time, so wait for the transition away from the
+        // current level. Should the level change before the interrupt is enabled, the transition
+        // already happened.
+
+        if self.read() {
+            self.wait_for_interrupt(Edge::FallingEdge, Some(false))
+                .await;
+        } else {
+            self.wait_for_interrupt(Edge::RisingEdge, Some(true)).await;
+        }
+
+        Ok(())
+    }
+}
+
 //
 // Crate functions
 //
@@ -348,7 +747,7 @@ impl<Pin: PinX> GpioPin<Pin, Disabled> {
 /// # Arguments
 /// `pin` - Provides the pin to configure
 /// `desired_mode` - Provides the desired mode of the pin.
-pub(crate) fn set_pin_function<Pin: PinIdWithMode>(pin: Pin, desired_mode: PinMode) {
+pub(crate) fn set_pin_function<Pin: PinIdWithMode>(pin: &Pin, desired_mode: PinMode) {
     let port = get_gpio_port(pin.get_port_name());
 
     let select_status = (desired_mode as usize) ^ (pin.get_mode() as usize);
@@ -379,6 +778,179 @@ pub(crate) fn set_pin_function<Pin: PinIdWithMode>(pin: Pin, desired_mode: PinMo
     }
 }
 
+//
+// Module private functions.
+//
+
+/// Configures a pin in high-impedance input mode.
+///
+/// # Arguments
+/// `pin` - Provides the pin to configure.
+fn configure_input_highz<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    port_regs
+        .resistor_enable
+        .get_bitband(pin.get_offset())
+        .write(false);
+
+    port_regs
+        .direction
+        .get_bitband(pin.get_offset())
+        .write(false);
+}
+
+/// Configures a pin in pull-up input mode.
+///
+/// # Arguments
+/// `pin` - Provides the pin to configure.
+fn configure_input_pullup<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    port_regs
+        .resistor_enable
+        .get_bitband(pin.get_offset())
+        .write(true);
+
+    port_regs
+        .direction
+        .get_bitband(pin.get_offset())
+        .write(false);
+    port_regs.output.get_bitband(pin.get_offset()).write(true);
+}
+
+/// Configures a pin in pull-down input mode.
+///
+/// # Arguments
+/// `pin` - Provides the pin to configure.
+fn configure_input_pulldown<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    port_regs
+        .resistor_enable
+        .get_bitband(pin.get_offset())
+        .write(true);
+
+    port_regs
+        .direction
+        .get_bitband(pin.get_offset())
+        .write(false);
+    port_regs.output.get_bitband(pin.get_offset()).write(false);
+}
+
+/// Configures a pin in push-pull output mode, driving it low.
+///
+/// # Arguments
+/// `pin` - Provides the pin to configure.
+fn configure_output_pushpull<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    port_regs.output.get_bitband(pin.get_offset()).write(false);
+
+    port_regs
+        .drive_strength
+        .get_bitband(pin.get_offset())
+        .write(false);
+
+    port_regs
+        .direction
+        .get_bitband(pin.get_offset())
+        .write(true);
+}
+
+/// Configures a pin in open collector output mode, driving it low.
+///
+/// # Arguments
+/// `pin` - Provides the pin to configure.
+fn configure_output_opencollector<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    port_regs.output.get_bitband(pin.get_offset()).write(false);
+
+    port_regs
+        .drive_strength
+        .get_bitband(pin.get_offset())
+        .write(false);
+
+    port_regs
+        .direction
+        .get_bitband(pin.get_offset())
+        .write(true);
+
+    port_regs
+        .resistor_enable
+        .get_bitband(pin.get_offset())
+        .write(true);
+}
+
+/// Configures a pin in a configuration selected at runtime.
+///
+/// # Arguments
+/// `pin` - Provides the pin to configure.
+/// `mode` - Provides the configuration.
+fn configure_dynamic_mode<Pin: PinId>(pin: &Pin, mode: DynamicMode) {
+    match mode {
+        DynamicMode::InputHighImpedance => configure_input_highz(pin),
+        DynamicMode::InputPullUp => configure_input_pullup(pin),
+        DynamicMode::InputPullDown => configure_input_pulldown(pin),
+        DynamicMode::OutputPushPull => configure_output_pushpull(pin),
+        DynamicMode::OutputOpenCollector => configure_output_opencollector(pin),
+    }
+}
+
+/// Toggles a pin configured in push-pull output mode.
+///
+/// # Arguments
+/// `pin` - Provides the pin to toggle.
+fn toggle_pushpull<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    // UNSAFE! Since the value is a boolean and only the bottom bit is being toggled, it is ok.
+    // This is to avoid the compiler making a clz + lsrs for the toggle instead of just an xor.
+
+    unsafe {
+        port_regs
+            .output
+            .get_bitband(pin.get_offset())
+            .modify_raw(|value| value ^ 1);
+    }
+}
+
+/// Releases a pin configured in open collector output mode, letting the pull-up resistor pull it
+/// high.
+///
+/// # Arguments
+/// `pin` - Provides the pin to release.
+fn release_opencollector<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    port_regs
+        .direction
+        .get_bitband(pin.get_offset())
+        .write(false);
+
+    compiler_fence(Ordering::Release);
+
+    port_regs.output.get_bitband(pin.get_offset()).write(true);
+}
+
+/// Drives a pin configured in open collector output mode low.
+///
+/// # Arguments
+/// `pin` - Provides the pin to drive.
+fn drive_opencollector_low<Pin: PinId>(pin: &Pin) {
+    let port_regs = get_gpio_port(pin.get_port_name());
+
+    port_regs.output.get_bitband(pin.get_offset()).write(false);
+
+    compiler_fence(Ordering::Release);
+
+    port_regs
+        .direction
+        .get_bitband(pin.get_offset())
+        .write(true);
+}
+
 //
 // For sealed traits.
 //
@@ -0,0 +1,234 @@
+//! # SafeState
+//! The `safestate` module includes structures and functions to declare the state GPIO pins should
+//! fall back to when they are released or when the application enters a fault condition, and to
+//! park unused pins.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::get_gpio_port, interrupt::nested_single_proc_critical_section, pin::get_port_index,
+};
+use core::sync::atomic::{AtomicU16, Ordering};
+
+//
+// Structures
+//
+
+/// Represents the state a GPIO pin is placed in when it is released.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SafeState {
+    OutputLow,
+    OutputHigh,
+    PullDown,
+    HighImpedance,
+}
+
+/// Represents the low-leakage state unused pins are placed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnusedPinState {
+    OutputLow,
+    PullDown,
+    PullUp,
+}
+
+//
+// Globals
+//
+
+/// Number of 16-bit ports (ports A, B, C, D, E and J).
+const PORT_COUNT: usize = 6;
+
+/// Names of the ports, in the order used to index the safe state tables.
+const PORT_NAMES: [char; PORT_COUNT] = ['A', 'B', 'C', 'D', 'E', 'J'];
+
+/// Pins of each port declared to be driven low when released.
+static SAFE_OUTPUT_LOW: [AtomicU16; PORT_COUNT] = [
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+];
+
+/// Pins of each port declared to be driven high when released.
+static SAFE_OUTPUT_HIGH: [AtomicU16; PORT_COUNT] = [
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+];
+
+/// Pins of each port declared to be pulled down when released.
+static SAFE_PULL_DOWN: [AtomicU16; PORT_COUNT] = [
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+];
+
+/// Pins of each port declared to be left in high-impedance when released.
+static SAFE_HIGH_IMPEDANCE: [AtomicU16; PORT_COUNT] = [
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+];
+
+//
+// Public functions.
+//
+
+/// Places every pin with a declared safe state in that state. Pins remain declared, so this can be
+/// called repeatedly. Intended to be called from panic and fault handlers.
+pub fn enter_safe_state() {
+    for (port_index, port_name) in PORT_NAMES.iter().enumerate() {
+        configure_safe_states(
+            *port_name,
+            SAFE_OUTPUT_LOW[port_index].load(Ordering::Relaxed),
+            SAFE_OUTPUT_HIGH[port_index].load(Ordering::Relaxed),
+            SAFE_PULL_DOWN[port_index].load(Ordering::Relaxed),
+            SAFE_HIGH_IMPEDANCE[port_index].load(Ordering::Relaxed),
+        );
+    }
+}
+
+//
+// Crate functions
+//
+
+/// Declares the safe state of given pins.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to declare the safe state for.
+/// `state` - Provides the safe state, or `None` to remove the declared safe state.
+pub(crate) fn declare_safe_state(port_name: char, mask: u16, state: Option<SafeState>) {
+    let port_index = get_port_index(port_name);
+
+    SAFE_OUTPUT_LOW[port_index].fetch_and(!mask, Ordering::Relaxed);
+    SAFE_OUTPUT_HIGH[port_index].fetch_and(!mask, Ordering::Relaxed);
+    SAFE_PULL_DOWN[port_index].fetch_and(!mask, Ordering::Relaxed);
+    SAFE_HIGH_IMPEDANCE[port_index].fetch_and(!mask, Ordering::Relaxed);
+
+    let declared_pins = match state {
+        Some(SafeState::OutputLow) => &SAFE_OUTPUT_LOW[port_index],
+        Some(SafeState::OutputHigh) => &SAFE_OUTPUT_HIGH[port_index],
+        Some(SafeState::PullDown) => &SAFE_PULL_DOWN[port_index],
+        Some(SafeState::HighImpedance) => &SAFE_HIGH_IMPEDANCE[port_index],
+        None => return,
+    };
+
+    declared_pins.fetch_or(mask, Ordering::Relaxed);
+}
+
+/// Places given pins in their declared safe state, and removes the declaration.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to release.
+///
+/// # Returns
+/// The pins that had a declared safe state.
+pub(crate) fn release_to_safe_state(port_name: char, mask: u16) -> u16 {
+    let port_index = get_port_index(port_name);
+
+    let output_low = SAFE_OUTPUT_LOW[port_index].fetch_and(!mask, Ordering::Relaxed) & mask;
+    let output_high = SAFE_OUTPUT_HIGH[port_index].fetch_and(!mask, Ordering::Relaxed) & mask;
+    let pull_down = SAFE_PULL_DOWN[port_index].fetch_and(!mask, Ordering::Relaxed) & mask;
+    let high_impedance = SAFE_HIGH_IMPEDANCE[port_index].fetch_and(!mask, Ordering::Relaxed) & mask;
+
+    configure_safe_states(
+        port_name,
+        output_low,
+        output_high,
+        pull_down,
+        high_impedance,
+    );
+
+    output_low | output_high | pull_down | high_impedance
+}
+
+/// Places given pins in a low-leakage state. Pins assigned to a peripheral are left untouched.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to configure.
+/// `state` - Provides the state to place the pins in.
+pub(crate) fn configure_unused_pins(port_name: char, mask: u16, state: UnusedPinState) {
+    let port_regs = get_gpio_port(port_name);
+    let mask = mask & !(port_regs.select_0.read() | port_regs.select_1.read());
+
+    if mask == 0 {
+        return;
+    }
+
+    match state {
+        UnusedPinState::OutputLow => {
+            port_regs.output.clear_bits(mask);
+            port_regs.direction.set_bits(mask);
+        }
+
+        UnusedPinState::PullDown => {
+            port_regs.output.clear_bits(mask);
+            port_regs.resistor_enable.set_bits(mask);
+            port_regs.direction.clear_bits(mask);
+        }
+
+        UnusedPinState::PullUp => {
+            port_regs.output.set_bits(mask);
+            port_regs.resistor_enable.set_bits(mask);
+            port_regs.direction.clear_bits(mask);
+        }
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Configures pins of a port in their safe states.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `output_low` - Provides the pins to drive low.
+/// `output_high` - Provides the pins to drive high.
+/// `pull_down` - Provides the pins to pull down.
+/// `high_impedance` - Provides the pins to leave in high-impedance.
+fn configure_safe_states(
+    port_name: char,
+    output_low: u16,
+    output_high: u16,
+    pull_down: u16,
+    high_impedance: u16,
+) {
+    if (output_low | output_high | pull_down | high_impedance) == 0 {
+        return;
+    }
+
+    let port_regs = get_gpio_port(port_name);
+
+    // Settle the output level before changing directions, so that pins switching to an output
+    // never glitch to the opposite level, and pins switching to a pull-down never get pulled up.
+    // The registers are shared with the other pins of the port, which interrupt service routines
+    // may be driving, so they are modified in a critical section. The pins may be released from a
+    // fault handler or from within a critical section, so interrupts are left as they were found.
+
+    nested_single_proc_critical_section(|_| {
+        port_regs.output.clear_bits(output_low | pull_down);
+        port_regs.output.set_bits(output_high);
+        port_regs.resistor_enable.set_bits(pull_down);
+        port_regs.resistor_enable.clear_bits(high_impedance);
+        port_regs
+            .direction
+            .modify(|value| (value | output_low | output_high) & !(pull_down | high_impedance));
+    });
+}
@@ -0,0 +1,46 @@
+//! # Instance
+//! The `instance` module maps the eUSCI_B modules to the pins carrying their I2C signals.
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{EusciB0, EusciB1, EusciB2, EusciB3, EusciBInstance},
+    pin::{Pin, PinX},
+};
+
+//
+// Traits
+//
+
+/// An eUSCI_B module operating in I2C mode.
+pub trait I2cInstance: EusciBInstance {
+    /// The pin carrying the serial data.
+    type SdaPin: PinX;
+
+    /// The pin carrying the serial clock.
+    type SclPin: PinX;
+}
+
+//
+// Structures
+//
+
+macro_rules! impl_i2c_instance {
+    ($($instance:ident, $sda:ty, $scl:ty;)+) => {
+        $(
+            impl I2cInstance for $instance {
+                type SdaPin = $sda;
+                type SclPin = $scl;
+            }
+        )+
+    };
+}
+
+impl_i2c_instance!(
+    EusciB0, Pin<'A', 6>, Pin<'A', 7>;
+    EusciB1, Pin<'C', 12>, Pin<'C', 13>;
+    EusciB2, Pin<'B', 6>, Pin<'B', 7>;
+    EusciB3, Pin<'E', 10>, Pin<'E', 11>;
+);
@@ -0,0 +1,554 @@
+//! # Master
+//! The `master` module includes structures and functions to utilize an eUSCI_B module as an I2C
+//! master.
+
+//
+// Dependencies
+//
+
+use crate::{
+    clock::ClockFrequencies,
+    eusci::{
+        configure_eusci, get_eusci_b_registers, select_prescaled_clock, UCRXIFG_MASK, UCSWRST_MASK,
+        UCSYNC_MASK, UCTXIFG_MASK,
+    },
+    i2c::{
+        I2cAddress, I2cError, I2cInstance, UCALIFG_MASK, UCCLTOIFG_MASK, UCMODE_I2C, UCMST_MASK,
+        UCNACKIFG_MASK, UCSLA10_MASK, UCTR_MASK, UCTXSTP_MASK, UCTXSTT_MASK,
+    },
+    pin::{AlternatePin, PinMode},
+};
+use embedded_hal::i2c::{ErrorType, I2c, Operation, SevenBitAddress, TenBitAddress};
+
+//
+// Consts
+//
+
+/// The clock low timeout of about 34 ms, past which a stretched clock aborts the transaction.
+const UCCLTO_34MS: u16 = 0x3 << 6;
+
+const UCBCNT_SHIFT: u16 = 8;
+
+//
+// Structures
+//
+
+/// Represents the standard I2C bus speeds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum I2cBusSpeed {
+    /// Standard mode, up to 100 kHz.
+    Standard = 100_000,
+
+    /// Fast mode, up to 400 kHz.
+    Fast = 400_000,
+
+    /// Fast mode plus, up to 1 MHz.
+    FastPlus = 1_000_000,
+}
+
+impl I2cBusSpeed {
+    /// Gets the highest clock frequency of the bus speed.
+    ///
+    /// # Returns
+    /// Frequency in Hz.
+    pub fn get_frequency(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// Represents an eUSCI_B module operating as an I2C master.
+pub struct I2cMaster<Instance: I2cInstance> {
+    /// The eUSCI_B module.
+    instance: Instance,
+
+    /// The serial data pin.
+    sda: AlternatePin<Instance::SdaPin>,
+
+    /// The serial clock pin.
+    scl: AlternatePin<Instance::SclPin>,
+
+    /// The control register value while idle, selecting the clock.
+    control: u16,
+
+    /// The clock prescaler.
+    prescaler: u16,
+
+    /// The actual clock frequency, in Hz.
+    frequency_hz: u32,
+}
+
+impl<Instance: I2cInstance> I2cMaster<Instance> {
+    /// Creates a new I2C master. The bus lines need external pull-up resistors.
+    ///
+    /// # Arguments
+    /// `instance` - Provides the eUSCI_B module.
+    /// `sda` - Provides the serial data pin of the module.
+    /// `scl` - Provides the serial clock pin of the module.
+    /// `speed` - Provides the bus speed. The actual clock frequency is the highest one the clocks
+    ///   can produce without exceeding it, when possible, and can be checked with `get_frequency`.
+    /// `clocks` - Provides the clock frequencies.
+    ///
+    /// # Returns
+    /// A new I2C master.
+    pub fn new(
+        instance: Instance,
+        sda: Instance::SdaPin,
+        scl: Instance::SclPin,
+        speed: I2cBusSpeed,
+        clocks: &ClockFrequencies,
+    ) -> Self {
+        let (source, prescaler, frequency_hz) =
+            select_prescaled_clock(clocks, speed.get_frequency());
+
+        let mut master = I2cMaster {
+            instance,
+            sda: AlternatePin::new(sda, PinMode::Alternate1),
+            scl: AlternatePin::new(scl, PinMode::Alternate1),
+            control: UCMODE_I2C | UCMST_MASK | UCSYNC_MASK | source.get_select_bits(),
+            prescaler,
+            frequency_hz,
+        };
+        master.reset();
+
+        master
+    }
+
+    /// Gets the actual clock frequency.
+    ///
+    /// # Returns
+    /// Frequency in Hz.
+    pub fn get_frequency(&self) -> u32 {
+        self.frequency_hz
+    }
+
+    /// Executes a sequence of operations on a slave device. A start condition and the address are
+    /// sent before the first operation and whenever the direction changes, and a stop condition
+    /// is sent after the last operation.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `operations` - Provides the operations to execute.
+    ///
+    /// # Returns
+    /// `Ok(())` if all operations completed.
+    /// `Err(I2cError)` if the transaction was aborted.
+    pub fn transaction(
+        &mut self,
+        address: I2cAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), I2cError> {
+        let result = self.execute_operations(address, operations);
+
+        match result {
+            // The module lost the bus, or is stuck on it, so it is reset to release the lines.
+            Err(I2cError::ArbitrationLost) | Err(I2cError::ClockStretchTimeout) => self.reset(),
+
+            Err(_) => {
+                let regs = get_eusci_b_registers::<Instance>();
+
+                regs.control_0.set_bits(UCTXSTP_MASK);
+                while (regs.control_0.read() & UCTXSTP_MASK) != 0 {}
+                regs.interrupt_flag.clear_bits(UCNACKIFG_MASK);
+            }
+
+            Ok(()) => (),
+        }
+
+        result
+    }
+
+    /// Writes bytes to a slave device.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `bytes` - Provides the bytes to write.
+    ///
+    /// # Returns
+    /// `Ok(())` if all bytes were acknowledged.
+    /// `Err(I2cError)` otherwise.
+    pub fn write(&mut self, address: I2cAddress, bytes: &[u8]) -> Result<(), I2cError> {
+        self.transaction(address, &mut [Operation::Write(bytes)])
+    }
+
+    /// Reads bytes from a slave device.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `buffer` - Receives the read bytes.
+    ///
+    /// # Returns
+    /// `Ok(())` if the buffer was filled.
+    /// `Err(I2cError)` otherwise.
+    pub fn read(&mut self, address: I2cAddress, buffer: &mut [u8]) -> Result<(), I2cError> {
+        self.transaction(address, &mut [Operation::Read(buffer)])
+    }
+
+    /// Writes bytes to a slave device, then reads bytes after a repeated start condition.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `bytes` - Provides the bytes to write.
+    /// `buffer` - Receives the read bytes.
+    ///
+    /// # Returns
+    /// `Ok(())` if all bytes were acknowledged and the buffer was filled.
+    /// `Err(I2cError)` otherwise.
+    pub fn write_read(
+        &mut self,
+        address: I2cAddress,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), I2cError> {
+        self.transaction(
+            address,
+            &mut [Operation::Write(bytes), Operation::Read(buffer)],
+        )
+    }
+
+    /// Holds the module in reset and releases its pins.
+    ///
+    /// # Returns
+    /// The eUSCI_B module, and the serial data and clock pins.
+    pub fn extract_parts(self) -> (Instance, Instance::SdaPin, Instance::SclPin) {
+        get_eusci_b_registers::<Instance>()
+            .control_0
+            .set_bits(UCSWRST_MASK);
+
+        (self.instance, self.sda.into_pin(), self.scl.into_pin())
+    }
+
+    /// Configures the module as a master, releasing the bus lines.
+    fn reset(&mut self) {
+        let control = self.control;
+        let prescaler = self.prescaler;
+
+        configure_eusci::<Instance, _>(|regs| {
+            let regs_b = get_eusci_b_registers::<Instance>();
+
+            // The module is held in reset while the configuration is written. Stops are sent
+            // manually, so the byte counter is only used to tell when a byte was acknowledged.
+
+            regs.control_0.write(control | UCSWRST_MASK);
+            regs_b.control_1.write(UCCLTO_34MS);
+            regs.baud_rate.write(prescaler);
+            regs.interrupt_enable.write(0);
+        });
+    }
+
+    /// Executes a sequence of operations, up to and including the final stop condition.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `operations` - Provides the operations to execute.
+    ///
+    /// # Returns
+    /// `Ok(())` if all operations completed.
+    /// `Err(I2cError)` if the transaction was aborted.
+    fn execute_operations(
+        &mut self,
+        address: I2cAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), I2cError> {
+        let regs = get_eusci_b_registers::<Instance>();
+
+        self.set_slave_address(address);
+        regs.interrupt_flag
+            .clear_bits(UCNACKIFG_MASK | UCALIFG_MASK | UCCLTOIFG_MASK);
+
+        // The direction of the transfer in progress, `true` when reading.
+
+        let mut direction = None;
+        let mut count = 0;
+
+        for index in 0..operations.len() {
+            let next_read = get_next_direction(operations, index);
+
+            match &mut operations[index] {
+                Operation::Write(bytes) => {
+                    if direction != Some(false) {
+                        self.start(false)?;
+                        direction = Some(false);
+                        count = 0;
+                    }
+
+                    for byte in bytes.iter() {
+                        // The address phase ends before the first byte is sent, so waiting for it
+                        // before queuing the second byte reports a NACK of the address as such.
+
+                        if count == 1 {
+                            self.wait_for_start()?;
+                        }
+
+                        self.wait_for_flag(UCTXIFG_MASK)?;
+                        regs.transmit_buffer.write(*byte as u16);
+                        count += 1;
+                    }
+
+                    // Before a stop or repeated start, the last byte must have been acknowledged.
+
+                    if next_read != Some(false) {
+                        self.wait_for_acknowledge(count)?;
+                    }
+                }
+
+                Operation::Read(buffer) => {
+                    if buffer.is_empty() {
+                        continue;
+                    }
+
+                    if direction != Some(true) {
+                        self.start(true)?;
+                        direction = Some(true);
+                    }
+
+                    // The stop or repeated start is requested while the last byte is received, so
+                    // that it is not acknowledged.
+
+                    let length = buffer.len();
+                    for (byte_index, byte) in buffer.iter_mut().enumerate() {
+                        if next_read != Some(true) && byte_index + 1 == length {
+                            match next_read {
+                                Some(_) => {
+                                    regs.control_0.set_bits(UCTR_MASK | UCTXSTT_MASK);
+                                    direction = Some(false);
+                                    count = 0;
+                                }
+                                None => regs.control_0.set_bits(UCTXSTP_MASK),
+                            }
+                        }
+
+                        self.wait_for_flag(UCRXIFG_MASK)?;
+                        *byte = regs.receive_buffer.read() as u8;
+                    }
+
+                    if direction == Some(false) {
+                        self.wait_for_start()?;
+                    }
+                }
+            }
+        }
+
+        // Without any start condition, there is nothing to stop. A transfer ending with a read
+        // already requested its stop.
+
+        match direction {
+            None => return Ok(()),
+            Some(false) => regs.control_0.set_bits(UCTXSTP_MASK),
+            Some(true) => (),
+        }
+
+        while (regs.control_0.read() & UCTXSTP_MASK) != 0 {}
+
+        Ok(())
+    }
+
+    /// Sets the address of the slave device, switching the addressing mode if needed.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    fn set_slave_address(&mut self, address: I2cAddress) {
+        let regs = get_eusci_b_registers::<Instance>();
+
+        let (value, control) = match address {
+            I2cAddress::SevenBit(address) => {
+                debug_assert!(address < 0x80);
+                (address as u16, self.control & !UCSLA10_MASK)
+            }
+
+            I2cAddress::TenBit(address) => {
+                debug_assert!(address < 0x400);
+                (address, self.control | UCSLA10_MASK)
+            }
+        };
+
+        // The addressing mode can only be changed in reset.
+
+        if control != self.control {
+            self.control = control;
+            self.reset();
+        }
+
+        regs.slave_address.write(value);
+    }
+
+    /// Generates a start condition, or a repeated start condition during a transfer, followed by
+    /// the address. The module sends the extra repeated start of 10-bit reads by itself.
+    ///
+    /// # Arguments
+    /// `read` - Provides whether the slave is addressed for a read.
+    ///
+    /// # Returns
+    /// `Ok(())` if the start condition was generated.
+    /// `Err(I2cError)` if the bus was lost, or the address of a read was not acknowledged.
+    fn start(&mut self, read: bool) -> Result<(), I2cError> {
+        let regs = get_eusci_b_registers::<Instance>();
+
+        if read {
+            regs.control_0
+                .modify(|value| (value & !UCTR_MASK) | UCTXSTT_MASK);
+
+            // Receiving starts right after the address, so it must be acknowledged first.
+
+            self.wait_for_start()
+        } else {
+            regs.control_0.set_bits(UCTR_MASK | UCTXSTT_MASK);
+
+            // The first byte is written while the address is sent, which is acknowledged along
+            // with the transmitted bytes.
+
+            Ok(())
+        }
+    }
+
+    /// Waits for the address to be sent after a start condition.
+    ///
+    /// # Returns
+    /// `Ok(())` if the slave acknowledged its address.
+    /// `Err(I2cError)` otherwise.
+    fn wait_for_start(&mut self) -> Result<(), I2cError> {
+        let regs = get_eusci_b_registers::<Instance>();
+
+        loop {
+            self.check_errors(I2cError::AddressNack)?;
+
+            if (regs.control_0.read() & UCTXSTT_MASK) == 0 {
+                return self.check_errors(I2cError::AddressNack);
+            }
+        }
+    }
+
+    /// Waits for an interrupt flag to be set.
+    ///
+    /// # Arguments
+    /// `mask` - Provides the interrupt flag.
+    ///
+    /// # Returns
+    /// `Ok(())` if the flag was set.
+    /// `Err(I2cError)` if the transaction was aborted first.
+    fn wait_for_flag(&mut self, mask: u16) -> Result<(), I2cError> {
+        let regs = get_eusci_b_registers::<Instance>();
+
+        loop {
+            self.check_errors(self.get_nack_error())?;
+
+            if (regs.interrupt_flag.read() & mask) != 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Waits for the bytes written since the last start condition to be acknowledged.
+    ///
+    /// # Arguments
+    /// `count` - Provides the number of bytes written.
+    ///
+    /// # Returns
+    /// `Ok(())` if all bytes were acknowledged.
+    /// `Err(I2cError)` otherwise.
+    fn wait_for_acknowledge(&mut self, count: usize) -> Result<(), I2cError> {
+        let regs = get_eusci_b_registers::<Instance>();
+
+        // Without any byte written, only the address is acknowledged.
+
+        self.wait_for_start()?;
+
+        // The byte counter only holds the lower 8 bits of the count.
+
+        while (regs.status.read() >> UCBCNT_SHIFT) as u8 != count as u8 {
+            self.check_errors(self.get_nack_error())?;
+        }
+
+        self.check_errors(I2cError::DataNack)
+    }
+
+    /// Gets the error reported if the slave does not acknowledge.
+    ///
+    /// # Returns
+    /// `I2cError::AddressNack` while the address is being sent.
+    /// `I2cError::DataNack` otherwise.
+    fn get_nack_error(&self) -> I2cError {
+        let regs = get_eusci_b_registers::<Instance>();
+
+        if (regs.control_0.read() & UCTXSTT_MASK) != 0 {
+            I2cError::AddressNack
+        } else {
+            I2cError::DataNack
+        }
+    }
+
+    /// Checks the interrupt flags for conditions aborting the transaction.
+    ///
+    /// # Arguments
+    /// `nack_error` - Provides the error reported if the slave did not acknowledge.
+    ///
+    /// # Returns
+    /// `Ok(())` if the transaction can go on.
+    /// `Err(I2cError)` otherwise.
+    fn check_errors(&self, nack_error: I2cError) -> Result<(), I2cError> {
+        let flags = get_eusci_b_registers::<Instance>().interrupt_flag.read();
+
+        if (flags & UCALIFG_MASK) != 0 {
+            Err(I2cError::ArbitrationLost)
+        } else if (flags & UCCLTOIFG_MASK) != 0 {
+            Err(I2cError::ClockStretchTimeout)
+        } else if (flags & UCNACKIFG_MASK) != 0 {
+            Err(nack_error)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Gets the direction of the next operation transferring bytes.
+///
+/// # Arguments
+/// `operations` - Provides the operations.
+/// `index` - Provides the index of the current operation.
+///
+/// # Returns
+/// `Some(true)` if the next operation reads bytes.
+/// `Some(false)` if the next operation writes bytes.
+/// `None` if no operation follows.
+fn get_next_direction(operations: &[Operation<'_>], index: usize) -> Option<bool> {
+    // Empty reads are skipped, as the module can only end a read on a received byte.
+
+    operations[index + 1..]
+        .iter()
+        .find_map(|operation| match operation {
+            Operation::Read([]) => None,
+            Operation::Read(_) => Some(true),
+            Operation::Write(_) => Some(false),
+        })
+}
+
+//
+// embedded-hal traits.
+//
+
+impl<Instance: I2cInstance> ErrorType for I2cMaster<Instance> {
+    type Error = I2cError;
+}
+
+impl<Instance: I2cInstance> I2c<SevenBitAddress> for I2cMaster<Instance> {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2cMaster::transaction(self, I2cAddress::SevenBit(address), operations)
+    }
+}
+
+impl<Instance: I2cInstance> I2c<TenBitAddress> for I2cMaster<Instance> {
+    fn transaction(
+        &mut self,
+        address: TenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2cMaster::transaction(self, I2cAddress::TenBit(address), operations)
+    }
+}
@@ -0,0 +1,86 @@
+//! # I2C
+//! The `i2c` module includes structures and functions to utilize the Inter-Integrated Circuit
+//! (I2C) protocol.
+
+//
+// Internal Modules
+//
+
+mod instance;
+mod master;
+mod slave;
+mod software;
+
+//
+// Reexports
+//
+
+pub use instance::*;
+pub use master::*;
+pub use slave::*;
+pub use software::*;
+
+//
+// Dependencies
+//
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+//
+// Consts
+//
+
+pub(crate) const UCA10_MASK: u16 = 1 << 15;
+pub(crate) const UCSLA10_MASK: u16 = 1 << 14;
+pub(crate) const UCMST_MASK: u16 = 1 << 11;
+pub(crate) const UCMODE_I2C: u16 = 0x3 << 9;
+pub(crate) const UCTR_MASK: u16 = 1 << 4;
+pub(crate) const UCTXSTP_MASK: u16 = 1 << 2;
+pub(crate) const UCTXSTT_MASK: u16 = 1 << 1;
+
+pub(crate) const UCSTTIFG_MASK: u16 = 1 << 2;
+pub(crate) const UCSTPIFG_MASK: u16 = 1 << 3;
+pub(crate) const UCALIFG_MASK: u16 = 1 << 4;
+pub(crate) const UCNACKIFG_MASK: u16 = 1 << 5;
+pub(crate) const UCCLTOIFG_MASK: u16 = 1 << 7;
+
+//
+// Structures
+//
+
+/// Represents the address of an I2C slave device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum I2cAddress {
+    /// A 7-bit address, without the read/write bit.
+    SevenBit(u8),
+
+    /// A 10-bit address.
+    TenBit(u16),
+}
+
+/// Represents an error that aborted an I2C transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum I2cError {
+    /// The slave did not acknowledge its address.
+    AddressNack,
+
+    /// The slave did not acknowledge a data byte.
+    DataNack,
+
+    /// Another master took over the bus.
+    ArbitrationLost,
+
+    /// A slave held the clock low for longer than the configured timeout.
+    ClockStretchTimeout,
+}
+
+impl embedded_hal::i2c::Error for I2cError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            I2cError::AddressNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            I2cError::DataNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            I2cError::ArbitrationLost => ErrorKind::ArbitrationLoss,
+            I2cError::ClockStretchTimeout => ErrorKind::Other,
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! # Slave
+//! The `slave` module includes structures and functions to utilize an eUSCI_B module as an I2C
+//! slave answering to up to four own addresses.
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{
+        configure_eusci, get_eusci_b_registers, get_eusci_index, set_eusci_interrupt_handler,
+        EUSCI_MODULE_COUNT, UCSWRST_MASK, UCSYNC_MASK,
+    },
+    i2c::{
+        I2cAddress, I2cInstance, UCA10_MASK, UCMODE_I2C, UCSTPIFG_MASK, UCSTTIFG_MASK, UCTR_MASK,
+    },
+    interrupt::single_proc_critical_section,
+    pin::{AlternatePin, PinMode},
+};
+use core::{
+    ptr::{addr_of, addr_of_mut},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+//
+// Consts
+//
+
+const UCOAEN_MASK: u16 = 1 << 10;
+
+/// The bits of a 10-bit address.
+const ADDRESS_MASK: u16 = 0x3FF;
+
+/// The receive interrupt flag of each own address.
+const UCRXIFG_MASKS: [u16; OWN_ADDRESS_COUNT] = [1 << 0, 1 << 8, 1 << 10, 1 << 12];
+
+/// The transmit interrupt flag of each own address.
+const UCTXIFG_MASKS: [u16; OWN_ADDRESS_COUNT] = [1 << 1, 1 << 9, 1 << 11, 1 << 13];
+
+/// The receive interrupt flags of the four own addresses.
+const UCRXIFG_ALL_MASK: u16 =
+    UCRXIFG_MASKS[0] | UCRXIFG_MASKS[1] | UCRXIFG_MASKS[2] | UCRXIFG_MASKS[3];
+
+/// The transmit interrupt flags of the four own addresses.
+const UCTXIFG_ALL_MASK: u16 =
+    UCTXIFG_MASKS[0] | UCTXIFG_MASKS[1] | UCTXIFG_MASKS[2] | UCTXIFG_MASKS[3];
+
+/// Values of the pending address match of an I2C slave.
+const NO_MATCH_PENDING: u8 = 0;
+const WRITE_MATCH_PENDING: u8 = 1;
+const READ_MATCH_PENDING: u8 = 2;
+
+/// The number of own addresses of an eUSCI_B module.
+const OWN_ADDRESS_COUNT: usize = 4;
+
+/// The byte sent when the master reads more bytes than the handler provides.
+const FILL_BYTE: u8 = 0xFF;
+
+//
+// Globals
+//
+
+/// Event handlers of the I2C slave of each eUSCI module.
+static mut I2C_SLAVE_HANDLERS: [Option<I2cSlaveHandler>; EUSCI_MODULE_COUNT] =
+    [None; EUSCI_MODULE_COUNT];
+
+/// Address matches of the I2C slave of each eUSCI module, not reported until the own address
+/// which matched is known.
+static PENDING_ADDRESS_MATCHES: [AtomicU8; EUSCI_MODULE_COUNT] =
+    [const { AtomicU8::new(NO_MATCH_PENDING) }; EUSCI_MODULE_COUNT];
+
+//
+// Structures
+//
+
+/// Represents an event on the bus concerning an I2C slave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum I2cSlaveEvent {
+    /// The master sent a start or repeated start condition followed by one of the own addresses.
+    /// Reported along with the first byte transferred, as the module then tells which own address
+    /// matched.
+    AddressMatched {
+        /// The index of the own address which matched, in the order they were given. When no byte
+        /// is transferred, the module does not tell which own address matched, so the first one
+        /// equal to the received address is reported, or else the first one equal once masked.
+        alias: usize,
+
+        /// Whether the master reads from the slave.
+        read: bool,
+    },
+
+    /// The master wrote a byte, which was acknowledged.
+    ByteReceived(u8),
+
+    /// The master reads a byte, which the handler should return. The module requests each byte
+    /// while the previous one is sent, so one more byte is requested than the master reads. The
+    /// byte returned for the last request before `Stop`, or before a repeated start, is discarded,
+    /// and a read pointer of an emulated peripheral should be moved back by one.
+    ByteRequested,
+
+    /// The master sent a stop condition.
+    Stop,
+}
+
+/// A handler invoked from the interrupt service routine for every event. The byte returned for
+/// `I2cSlaveEvent::ByteRequested` is sent to the master, and the one returned for other events is
+/// ignored. Sends `0xFF` if `None` is returned.
+pub type I2cSlaveHandler = fn(I2cSlaveEvent) -> Option<u8>;
+
+/// Represents an eUSCI_B module operating as an I2C slave. The clock is stretched while the
+/// handler runs.
+pub struct I2cSlave<Instance: I2cInstance> {
+    /// The eUSCI_B module.
+    instance: Instance,
+
+    /// The serial data pin.
+    sda: AlternatePin<Instance::SdaPin>,
+
+    /// The serial clock pin.
+    scl: AlternatePin<Instance::SclPin>,
+}
+
+impl<Instance: I2cInstance> I2cSlave<Instance> {
+    /// Creates a new I2C slave, and `dispatch_eusci_interrupt` should be called from the
+    /// interrupt service routine of the module.
+    ///
+    /// # Arguments
+    /// `instance` - Provides the eUSCI_B module.
+    /// `sda` - Provides the serial data pin of the module.
+    /// `scl` - Provides the serial clock pin of the module.
+    /// `addresses` - Provides one to four own addresses, either all 7-bit or all 10-bit.
+    /// `address_mask` - Provides the bits of the received address compared with the own
+    ///   addresses. Cleared bits are not compared, so an own address answers to several addresses.
+    ///   `0x3FF` compares every bit.
+    /// `handler` - Provides the function invoked for every event.
+    ///
+    /// # Returns
+    /// A new I2C slave.
+    pub fn new(
+        instance: Instance,
+        sda: Instance::SdaPin,
+        scl: Instance::SclPin,
+        addresses: &[I2cAddress],
+        address_mask: u16,
+        handler: I2cSlaveHandler,
+    ) -> Self {
+        debug_assert!(!addresses.is_empty() && addresses.len() <= OWN_ADDRESS_COUNT);
+
+        let ten_bit = matches!(addresses.first(), Some(I2cAddress::TenBit(_)));
+
+        configure_eusci::<Instance, _>(|regs| {
+            let regs_b = get_eusci_b_registers::<Instance>();
+
+            let mut control = UCMODE_I2C | UCSYNC_MASK;
+            if ten_bit {
+                control |= UCA10_MASK;
+            }
+
+            regs.control_0.write(control | UCSWRST_MASK);
+            regs_b.address_mask.write(address_mask & ADDRESS_MASK);
+
+            for (index, own_address) in regs_b.own_address.iter().enumerate() {
+                let value = match addresses.get(index) {
+                    Some(I2cAddress::SevenBit(address)) => {
+                        debug_assert!(!ten_bit && *address < 0x80);
+                        *address as u16 | UCOAEN_MASK
+                    }
+                    Some(I2cAddress::TenBit(address)) => {
+                        debug_assert!(ten_bit && *address < 0x400);
+                        *address | UCOAEN_MASK
+                    }
+                    None => 0,
+                };
+
+                own_address.write(value);
+            }
+        });
+
+        get_pending_match::<Instance>().store(NO_MATCH_PENDING, Ordering::Relaxed);
+        set_event_handler::<Instance>(Some(handler));
+        set_eusci_interrupt_handler::<Instance>(Some(service_interrupt::<Instance>));
+        get_eusci_b_registers::<Instance>()
+            .interrupt_enable
+            .write(UCRXIFG_ALL_MASK | UCTXIFG_ALL_MASK | UCSTTIFG_MASK | UCSTPIFG_MASK);
+
+        I2cSlave {
+            instance,
+            sda: AlternatePin::new(sda, PinMode::Alternate1),
+            scl: AlternatePin::new(scl, PinMode::Alternate1),
+        }
+    }
+
+    /// Holds the module in reset, unregisters the handler and releases the pins.
+    ///
+    /// # Returns
+    /// The eUSCI_B module, and the serial data and clock pins.
+    pub fn extract_parts(self) -> (Instance, Instance::SdaPin, Instance::SclPin) {
+        get_eusci_b_registers::<Instance>()
+            .control_0
+            .set_bits(UCSWRST_MASK);
+        set_eusci_interrupt_handler::<Instance>(None);
+        set_event_handler::<Instance>(None);
+
+        (self.instance, self.sda.into_pin(), self.scl.into_pin())
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Registers the event handler of the I2C slave of an eUSCI module.
+///
+/// # Arguments
+/// `handler` - Provides the event handler, or `None` to unregister the current one.
+fn set_event_handler<Instance: I2cInstance>(handler: Option<I2cSlaveHandler>) {
+    single_proc_critical_section(|_| unsafe {
+        (*addr_of_mut!(I2C_SLAVE_HANDLERS))[get_eusci_index::<Instance>()] = handler;
+    });
+}
+
+/// Gets the pending address match of the I2C slave of an eUSCI module.
+///
+/// # Returns
+/// The pending address match shared with the interrupt service routine.
+fn get_pending_match<Instance: I2cInstance>() -> &'static AtomicU8 {
+    &PENDING_ADDRESS_MATCHES[get_eusci_index::<Instance>()]
+}
+
+/// Reports the pending address match, if any.
+///
+/// # Arguments
+/// `handler` - Provides the event handler.
+/// `alias` - Provides the index of the own address which matched, or `None` to guess it from the
+///   received address.
+fn report_pending_match<Instance: I2cInstance>(handler: I2cSlaveHandler, alias: Option<usize>) {
+    let read = match get_pending_match::<Instance>().swap(NO_MATCH_PENDING, Ordering::Relaxed) {
+        WRITE_MATCH_PENDING => false,
+        READ_MATCH_PENDING => true,
+        _ => return,
+    };
+
+    handler(I2cSlaveEvent::AddressMatched {
+        alias: alias.unwrap_or_else(get_matched_alias::<Instance>),
+        read,
+    });
+}
+
+/// Gets the own address whose interrupt flag is set.
+///
+/// # Arguments
+/// `flags` - Provides the interrupt flags.
+/// `masks` - Provides the receive or transmit interrupt flag of each own address.
+///
+/// # Returns
+/// The index of the own address.
+fn get_flag_alias(flags: u16, masks: &[u16; OWN_ADDRESS_COUNT]) -> usize {
+    masks
+        .iter()
+        .position(|mask| (flags & mask) != 0)
+        .unwrap_or(0)
+}
+
+/// Guesses the own address matching the received address, when no interrupt flag tells it.
+///
+/// # Returns
+/// The index of the first own address equal to the received address, or else of the first one
+/// equal once masked.
+fn get_matched_alias<Instance: I2cInstance>() -> usize {
+    let regs = get_eusci_b_registers::<Instance>();
+    let received = regs.received_address.read() & ADDRESS_MASK;
+    let mask = regs.address_mask.read() & ADDRESS_MASK;
+
+    let matches = |index: &usize, mask: u16| {
+        let own_address = regs.own_address[*index].read();
+
+        (own_address & UCOAEN_MASK) != 0 && ((own_address ^ received) & mask) == 0
+    };
+
+    // An exact match takes precedence over a masked one.
+
+    (0..OWN_ADDRESS_COUNT)
+        .find(|index| matches(index, ADDRESS_MASK))
+        .or_else(|| (0..OWN_ADDRESS_COUNT).find(|index| matches(index, mask)))
+        .unwrap_or(0)
+}
+
+/// Services the interrupts of an I2C slave, reporting the events to its handler in bus order.
+fn service_interrupt<Instance: I2cInstance>() {
+    let regs = get_eusci_b_registers::<Instance>();
+    let flags = regs.interrupt_flag.read() & regs.interrupt_enable.read();
+    let handler = unsafe { (*addr_of!(I2C_SLAVE_HANDLERS))[get_eusci_index::<Instance>()] };
+
+    let Some(handler) = handler else {
+        return;
+    };
+
+    // A byte received completes the transfer preceding any stop or repeated start. Address
+    // matches are reported with the first byte transferred, whose flag tells the own address.
+
+    if (flags & UCRXIFG_ALL_MASK) != 0 {
+        report_pending_match::<Instance>(handler, Some(get_flag_alias(flags, &UCRXIFG_MASKS)));
+        handler(I2cSlaveEvent::ByteReceived(regs.receive_buffer.read() as u8));
+    }
+
+    if (flags & UCSTPIFG_MASK) != 0 {
+        regs.interrupt_flag.clear_bits(UCSTPIFG_MASK);
+        report_pending_match::<Instance>(handler, None);
+        handler(I2cSlaveEvent::Stop);
+    }
+
+    if (flags & UCSTTIFG_MASK) != 0 {
+        regs.interrupt_flag.clear_bits(UCSTTIFG_MASK);
+        report_pending_match::<Instance>(handler, None);
+
+        let pending = if (regs.control_0.read() & UCTR_MASK) != 0 {
+            READ_MATCH_PENDING
+        } else {
+            WRITE_MATCH_PENDING
+        };
+        get_pending_match::<Instance>().store(pending, Ordering::Relaxed);
+    }
+
+    if (flags & UCTXIFG_ALL_MASK) != 0 {
+        report_pending_match::<Instance>(handler, Some(get_flag_alias(flags, &UCTXIFG_MASKS)));
+
+        let byte = handler(I2cSlaveEvent::ByteRequested).unwrap_or(FILL_BYTE);
+        regs.transmit_buffer.write(byte as u16);
+    }
+}
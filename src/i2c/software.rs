@@ -0,0 +1,477 @@
+//! # Software
+//! The `software` module includes structures and functions to utilize an I2C master driven by
+//! software over open-collector GPIO pins.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{GpioOut, GpioPin, GpioPinInput, GpioPinOutput, OpenCollector},
+    i2c::{I2cAddress, I2cError},
+    pin::PinX,
+};
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{ErrorType, I2c, Operation, SevenBitAddress, TenBitAddress},
+};
+
+//
+// Consts
+//
+
+/// Read/write bit of an address byte, set for reads.
+const READ_BIT: u8 = 0x01;
+
+/// Header of the first address byte of a 10-bit address.
+const TEN_BIT_ADDRESS_HEADER: u8 = 0xF0;
+
+/// A GPIO pin driving an I2C line.
+type I2cLinePin<Pin> = GpioPin<Pin, GpioOut<OpenCollector>>;
+
+//
+// Structures
+//
+
+/// Represents an I2C master that drives the bus by toggling open-collector GPIO pins.
+pub struct SoftwareI2c<Scl: PinX, Sda: PinX, Delay: DelayNs> {
+    /// The serial clock line.
+    scl: I2cLinePin<Scl>,
+
+    /// The serial data line.
+    sda: I2cLinePin<Sda>,
+
+    /// The delay source used to pace the clock.
+    delay: Delay,
+
+    /// The time between two clock edges, in nanoseconds.
+    half_period_ns: u32,
+
+    /// The longest time a slave may hold the clock low, in microseconds.
+    stretch_timeout_us: u32,
+}
+
+impl<Scl: PinX, Sda: PinX, Delay: DelayNs> SoftwareI2c<Scl, Sda, Delay> {
+    /// Creates a new software I2C master. Both lines are released.
+    ///
+    /// # Arguments
+    /// `scl` - Provides the serial clock pin.
+    /// `sda` - Provides the serial data pin.
+    /// `delay` - Provides the delay source used to pace the clock.
+    /// `frequency_hz` - Provides the desired clock frequency. The actual frequency is lower, as
+    ///   toggling the pins takes time in addition to the delays.
+    /// `stretch_timeout_us` - Provides the longest time a slave may hold the clock low.
+    ///
+    /// # Returns
+    /// A new software I2C master.
+    pub fn new(
+        mut scl: I2cLinePin<Scl>,
+        mut sda: I2cLinePin<Sda>,
+        delay: Delay,
+        frequency_hz: u32,
+        stretch_timeout_us: u32,
+    ) -> Self {
+        debug_assert!(frequency_hz > 0);
+
+        scl.set();
+        sda.set();
+
+        SoftwareI2c {
+            scl,
+            sda,
+            delay,
+            half_period_ns: 500_000_000 / frequency_hz,
+            stretch_timeout_us,
+        }
+    }
+
+    /// Generates a start condition. Generates a repeated start condition when called during a
+    /// transfer.
+    ///
+    /// # Returns
+    /// `Ok(())` if the bus was taken.
+    /// `Err(I2cError)` if the bus is held by another master or slave.
+    pub fn start(&mut self) -> Result<(), I2cError> {
+        self.sda.set();
+        self.delay.delay_ns(self.half_period_ns);
+        self.release_clock()?;
+
+        if !self.sda.read() {
+            return Err(I2cError::ArbitrationLost);
+        }
+
+        self.delay.delay_ns(self.half_period_ns);
+        self.sda.clear();
+        self.delay.delay_ns(self.half_period_ns);
+        self.scl.clear();
+        Ok(())
+    }
+
+    /// Generates a stop condition, releasing the bus.
+    ///
+    /// # Returns
+    /// `Ok(())` if the bus was released.
+    /// `Err(I2cError)` if the stop condition could not be generated.
+    pub fn stop(&mut self) -> Result<(), I2cError> {
+        self.sda.clear();
+        self.delay.delay_ns(self.half_period_ns);
+        self.release_clock()?;
+        self.delay.delay_ns(self.half_period_ns);
+        self.sda.set();
+        self.delay.delay_ns(self.half_period_ns);
+
+        if !self.sda.read() {
+            return Err(I2cError::ArbitrationLost);
+        }
+
+        Ok(())
+    }
+
+    /// Sends a byte and reads the acknowledge bit of the receiver.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the byte to send.
+    ///
+    /// # Returns
+    /// `Ok(true)` if the receiver acknowledged the byte.
+    /// `Ok(false)` if the receiver did not acknowledge the byte.
+    /// `Err(I2cError)` if the byte could not be sent.
+    pub fn write_byte(&mut self, byte: u8) -> Result<bool, I2cError> {
+        for bit in (0..8).rev() {
+            self.write_bit((byte >> bit) & 1 != 0)?;
+        }
+
+        Ok(!self.read_bit()?)
+    }
+
+    /// Receives a byte and sends an acknowledge bit.
+    ///
+    /// # Arguments
+    /// `acknowledge` - Provides whether the byte is acknowledged. The last byte of a read should
+    ///   not be acknowledged.
+    ///
+    /// # Returns
+    /// `Ok(byte)` with the received byte.
+    /// `Err(I2cError)` if the byte could not be received.
+    pub fn read_byte(&mut self, acknowledge: bool) -> Result<u8, I2cError> {
+        let mut byte = 0;
+
+        for _ in 0..8 {
+            byte = (byte << 1) | (self.read_bit()? as u8);
+        }
+
+        self.write_bit(!acknowledge)?;
+        Ok(byte)
+    }
+
+    /// Executes a sequence of operations on a slave device. A start condition and the address are
+    /// sent before the first operation and whenever the direction changes, and a stop condition
+    /// is sent after the last operation.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `operations` - Provides the operations to execute.
+    ///
+    /// # Returns
+    /// `Ok(())` if all operations completed.
+    /// `Err(I2cError)` if the transaction was aborted.
+    pub fn transaction(
+        &mut self,
+        address: I2cAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), I2cError> {
+        let result = self.execute_operations(address, operations);
+
+        match result {
+            // The bus is no longer ours, so only release the lines.
+            Err(I2cError::ArbitrationLost) | Err(I2cError::ClockStretchTimeout) => {
+                self.scl.set();
+                self.sda.set();
+                result.map(|_| ())
+            }
+
+            Err(_) => {
+                let _ = self.stop();
+                result.map(|_| ())
+            }
+
+            Ok(true) => self.stop(),
+
+            // No start condition was sent, so the bus is left idle.
+            Ok(false) => Ok(()),
+        }
+    }
+
+    /// Writes bytes to a slave device.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `bytes` - Provides the bytes to write.
+    ///
+    /// # Returns
+    /// `Ok(())` if all bytes were acknowledged.
+    /// `Err(I2cError)` otherwise.
+    pub fn write(&mut self, address: I2cAddress, bytes: &[u8]) -> Result<(), I2cError> {
+        self.transaction(address, &mut [Operation::Write(bytes)])
+    }
+
+    /// Reads bytes from a slave device.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `buffer` - Receives the read bytes.
+    ///
+    /// # Returns
+    /// `Ok(())` if the buffer was filled.
+    /// `Err(I2cError)` otherwise.
+    pub fn read(&mut self, address: I2cAddress, buffer: &mut [u8]) -> Result<(), I2cError> {
+        self.transaction(address, &mut [Operation::Read(buffer)])
+    }
+
+    /// Writes bytes to a slave device, then reads bytes after a repeated start condition.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `bytes` - Provides the bytes to write.
+    /// `buffer` - Receives the read bytes.
+    ///
+    /// # Returns
+    /// `Ok(())` if all bytes were acknowledged and the buffer was filled.
+    /// `Err(I2cError)` otherwise.
+    pub fn write_read(
+        &mut self,
+        address: I2cAddress,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), I2cError> {
+        self.transaction(
+            address,
+            &mut [Operation::Write(bytes), Operation::Read(buffer)],
+        )
+    }
+
+    /// Extracts the pins and the delay source.
+    ///
+    /// # Returns
+    /// The clock and data pins, and the delay source.
+    pub fn extract_parts(self) -> (I2cLinePin<Scl>, I2cLinePin<Sda>, Delay) {
+        (self.scl, self.sda, self.delay)
+    }
+
+    /// Executes a sequence of operations, without the final stop condition. Empty reads are
+    /// skipped.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `operations` - Provides the operations to execute.
+    ///
+    /// # Returns
+    /// `Ok(true)` if all operations completed after a start condition.
+    /// `Ok(false)` if there was nothing to send, so no start condition was sent.
+    /// `Err(I2cError)` if the transaction was aborted.
+    fn execute_operations(
+        &mut self,
+        address: I2cAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<bool, I2cError> {
+        let mut previous_read = None;
+
+        for index in 0..operations.len() {
+            let next_read = operations[index + 1..]
+                .iter()
+                .find_map(|operation| match operation {
+                    Operation::Read([]) => None,
+                    Operation::Read(_) => Some(true),
+                    Operation::Write(_) => Some(false),
+                })
+                == Some(true);
+
+            match &mut operations[index] {
+                Operation::Write(bytes) => {
+                    if previous_read != Some(false) {
+                        self.start()?;
+                        self.send_address(address, false)?;
+                    }
+
+                    for byte in bytes.iter() {
+                        if !self.write_byte(*byte)? {
+                            return Err(I2cError::DataNack);
+                        }
+                    }
+
+                    previous_read = Some(false);
+                }
+
+                Operation::Read(buffer) => {
+                    if buffer.is_empty() {
+                        continue;
+                    }
+
+                    if previous_read != Some(true) {
+                        self.start()?;
+                        self.send_address(address, true)?;
+                    }
+
+                    // The last byte before a stop or a direction change is not acknowledged.
+
+                    let length = buffer.len();
+                    for (byte_index, byte) in buffer.iter_mut().enumerate() {
+                        let last = !next_read && (byte_index + 1 == length);
+                        *byte = self.read_byte(!last)?;
+                    }
+
+                    previous_read = Some(true);
+                }
+            }
+        }
+
+        Ok(previous_read.is_some())
+    }
+
+    /// Sends the address of a slave device after a start condition.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address of the slave device.
+    /// `read` - Provides whether the slave is addressed for a read.
+    ///
+    /// # Returns
+    /// `Ok(())` if the slave acknowledged its address.
+    /// `Err(I2cError)` otherwise.
+    fn send_address(&mut self, address: I2cAddress, read: bool) -> Result<(), I2cError> {
+        let read_bit = if read { READ_BIT } else { 0 };
+
+        match address {
+            I2cAddress::SevenBit(address) => {
+                debug_assert!(address < 0x80);
+                self.send_address_byte((address << 1) | read_bit)
+            }
+
+            I2cAddress::TenBit(address) => {
+                debug_assert!(address < 0x400);
+
+                // A 10-bit address is always sent for a write. Reads then repeat the start
+                // condition and send the header alone with the read bit set.
+
+                let header = TEN_BIT_ADDRESS_HEADER | ((address >> 7) as u8 & 0x06);
+                self.send_address_byte(header)?;
+                self.send_address_byte(address as u8)?;
+
+                if read {
+                    self.start()?;
+                    self.send_address_byte(header | READ_BIT)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Sends a byte of an address.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the address byte.
+    ///
+    /// # Returns
+    /// `Ok(())` if the slave acknowledged the byte.
+    /// `Err(I2cError)` otherwise.
+    fn send_address_byte(&mut self, byte: u8) -> Result<(), I2cError> {
+        if self.write_byte(byte)? {
+            Ok(())
+        } else {
+            Err(I2cError::AddressNack)
+        }
+    }
+
+    /// Sends a single bit over a full clock period.
+    ///
+    /// # Arguments
+    /// `bit` - Provides the bit to send.
+    ///
+    /// # Returns
+    /// `Ok(())` if the bit was sent.
+    /// `Err(I2cError)` otherwise.
+    fn write_bit(&mut self, bit: bool) -> Result<(), I2cError> {
+        if bit {
+            self.sda.set();
+        } else {
+            self.sda.clear();
+        }
+
+        self.delay.delay_ns(self.half_period_ns);
+        self.release_clock()?;
+
+        // A released data line read back low means another master is driving it.
+
+        if bit && !self.sda.read() {
+            return Err(I2cError::ArbitrationLost);
+        }
+
+        self.delay.delay_ns(self.half_period_ns);
+        self.scl.clear();
+        Ok(())
+    }
+
+    /// Receives a single bit over a full clock period.
+    ///
+    /// # Returns
+    /// `Ok(bit)` with the received bit.
+    /// `Err(I2cError)` otherwise.
+    fn read_bit(&mut self) -> Result<bool, I2cError> {
+        self.sda.set();
+        self.delay.delay_ns(self.half_period_ns);
+        self.release_clock()?;
+        let bit = self.sda.read();
+        self.delay.delay_ns(self.half_period_ns);
+        self.scl.clear();
+        Ok(bit)
+    }
+
+    /// Releases the clock line and waits for slaves to stop stretching it.
+    ///
+    /// # Returns
+    /// `Ok(())` if the clock line went high.
+    /// `Err(I2cError)` if the clock line was held low past the timeout.
+    fn release_clock(&mut self) -> Result<(), I2cError> {
+        self.scl.set();
+
+        let mut remaining_us = self.stretch_timeout_us;
+        while !self.scl.read() {
+            if remaining_us == 0 {
+                return Err(I2cError::ClockStretchTimeout);
+            }
+
+            remaining_us -= 1;
+            self.delay.delay_us(1);
+        }
+
+        Ok(())
+    }
+}
+
+//
+// embedded-hal traits.
+//
+
+impl<Scl: PinX, Sda: PinX, Delay: DelayNs> ErrorType for SoftwareI2c<Scl, Sda, Delay> {
+    type Error = I2cError;
+}
+
+impl<Scl: PinX, Sda: PinX, Delay: DelayNs> I2c<SevenBitAddress> for SoftwareI2c<Scl, Sda, Delay> {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        SoftwareI2c::transaction(self, I2cAddress::SevenBit(address), operations)
+    }
+}
+
+impl<Scl: PinX, Sda: PinX, Delay: DelayNs> I2c<TenBitAddress> for SoftwareI2c<Scl, Sda, Delay> {
+    fn transaction(
+        &mut self,
+        address: TenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        SoftwareI2c::transaction(self, I2cAddress::TenBit(address), operations)
+    }
+}
@@ -20,6 +20,16 @@ fn enable_interrupts() {
     compiler_fence(Ordering::SeqCst);
 }
 
+/// Gets whether interrupts are globally disabled.
+///
+/// # Returns
+/// `true` if the PRIMASK bit is set.
+fn are_interrupts_disabled() -> bool {
+    let primask: u32;
+    unsafe { asm!("mrs {}, PRIMASK", out(reg) primask) };
+    (primask & 1) != 0
+}
+
 /// Creates a single processor crtitical section.
 ///
 /// # Arguments
@@ -33,3 +43,23 @@ pub fn single_proc_critical_section<F: FnMut(SingleProcessorCriticalSectionToken
     crtitical_section_function(critical_section_token);
     enable_interrupts();
 }
+
+/// Creates a single processor critical section which may be nested in another one, or entered
+/// from a fault handler. Interrupts are only enabled again if they were enabled on entry.
+///
+/// # Arguments
+/// `crtitical_section_function` - Provides a function to be executed in the context of a critical
+///     section.
+pub fn nested_single_proc_critical_section<F: FnMut(SingleProcessorCriticalSectionToken)>(
+    mut crtitical_section_function: F,
+) {
+    let were_disabled = are_interrupts_disabled();
+
+    disable_interrupts();
+    let critical_section_token = SingleProcessorCriticalSectionToken { _unused: () };
+    crtitical_section_function(critical_section_token);
+
+    if !were_disabled {
+        enable_interrupts();
+    }
+}
@@ -0,0 +1,413 @@
+//! # Keypad
+//! The `keypad` module includes structures and functions to scan matrix keypads wired to a row
+//! output bus and a pulled-up column input bus.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{
+        GpioBusInput, GpioBusInterrupt, GpioBusOutput, GpioIn, GpioOut, GpioPushPullMode,
+        GpioSectionBus, OpenCollector, PullUp,
+    },
+    pin::{PortSectionWithInterrupt, PortSectionX},
+    Edge,
+};
+use embedded_hal::delay::DelayNs;
+
+//
+// Consts
+//
+
+/// Time for the columns to settle after selecting a row, in microseconds.
+const ROW_SETTLE_US: u32 = 5;
+
+/// Default number of consecutive scans a key must keep its new state to be reported.
+const DEFAULT_DEBOUNCE_SCANS: u8 = 3;
+
+//
+// Traits
+//
+
+/// A GPIO Bus that can drive the rows of a keypad.
+pub trait KeypadRows<const ROWS: usize>: private::Sealed {
+    /// Drives the selected rows low, and drives the other rows high or releases them.
+    ///
+    /// # Arguments
+    /// `selected_mask` - Provides the rows to select.
+    fn select_rows(&mut self, selected_mask: usize);
+}
+
+//
+// Structures
+//
+
+/// Push-pull rows drive the unselected rows high, so two pressed keys in the same column short a
+/// high row to the selected low row. Each row then needs a series resistor or diode to limit the
+/// current, which open-collector rows do not, as they release the unselected rows.
+impl<const ROWS: usize, Section: PortSectionX<ROWS>, OutputMode: GpioPushPullMode> KeypadRows<ROWS>
+    for GpioSectionBus<ROWS, Section, GpioOut<OutputMode>>
+{
+    fn select_rows(&mut self, selected_mask: usize) {
+        self.write(!selected_mask);
+    }
+}
+
+impl<const ROWS: usize, Section: PortSectionX<ROWS>> KeypadRows<ROWS>
+    for GpioSectionBus<ROWS, Section, GpioOut<OpenCollector>>
+{
+    fn select_rows(&mut self, selected_mask: usize) {
+        self.release_bits(!selected_mask);
+        self.drive_bits_low(selected_mask);
+    }
+}
+
+/// Represents the position of a key in the matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyPosition {
+    /// The row of the key, starting from 0.
+    pub row: u8,
+
+    /// The column of the key, starting from 0.
+    pub column: u8,
+}
+
+/// Represents a change reported by a keypad scan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeypadEvent {
+    /// A key was pressed.
+    Pressed(KeyPosition),
+
+    /// A key was released.
+    Released(KeyPosition),
+
+    /// A key is still held down, and repeats.
+    Repeated(KeyPosition),
+
+    /// Several keys form a rectangle in the matrix, so the pressed keys cannot be told apart from
+    /// phantom keys. Scans are ignored until the ambiguity clears.
+    Ghosting,
+}
+
+/// Represents the auto-repeat timing of held keys.
+#[derive(Clone, Copy)]
+struct KeypadRepeat {
+    /// The number of scans between a press and the first repeat.
+    delay_scans: u16,
+
+    /// The number of scans between two repeats.
+    interval_scans: u16,
+}
+
+/// Represents a matrix keypad. A key connects its row to its column, so a pressed key pulls its
+/// column low while its row is selected.
+pub struct Keypad<const ROWS: usize, const COLUMNS: usize, Rows, ColumnSection, Delay>
+where
+    Rows: KeypadRows<ROWS>,
+    ColumnSection: PortSectionX<COLUMNS>,
+    Delay: DelayNs,
+{
+    /// The row output bus.
+    rows: Rows,
+
+    /// The column input bus.
+    columns: GpioSectionBus<COLUMNS, ColumnSection, GpioIn<PullUp>>,
+
+    /// The delay source used to let the columns settle.
+    delay: Delay,
+
+    /// The debounced state of the keys. Bit `column` of each row is set for pressed keys.
+    pressed: [u16; ROWS],
+
+    /// The number of consecutive scans each key differed from its debounced state.
+    debounce_counts: [[u8; COLUMNS]; ROWS],
+
+    /// The number of scans left before each held key repeats.
+    repeat_counts: [[u16; COLUMNS]; ROWS],
+
+    /// The number of consecutive scans a key must keep its new state to be reported.
+    debounce_scans: u8,
+
+    /// The auto-repeat timing, if enabled.
+    repeat: Option<KeypadRepeat>,
+
+    /// Whether the last scan was ambiguous.
+    ghosting: bool,
+
+    /// Whether all rows are kept selected between scans, so any key raises a column interrupt.
+    wake_enabled: bool,
+}
+
+impl<const ROWS: usize, const COLUMNS: usize, Rows, ColumnSection, Delay>
+    Keypad<ROWS, COLUMNS, Rows, ColumnSection, Delay>
+where
+    Rows: KeypadRows<ROWS>,
+    ColumnSection: PortSectionX<COLUMNS>,
+    Delay: DelayNs,
+{
+    /// Creates a new keypad, with all keys released and auto-repeat disabled.
+    ///
+    /// # Arguments
+    /// `rows` - Provides the row output bus.
+    /// `columns` - Provides the column input bus.
+    /// `delay` - Provides the delay source used to let the columns settle.
+    ///
+    /// # Returns
+    /// A new keypad.
+    pub fn new(
+        mut rows: Rows,
+        columns: GpioSectionBus<COLUMNS, ColumnSection, GpioIn<PullUp>>,
+        delay: Delay,
+    ) -> Self {
+        debug_assert!(COLUMNS <= 16);
+
+        rows.select_rows(0);
+
+        Keypad {
+            rows,
+            columns,
+            delay,
+            pressed: [0; ROWS],
+            debounce_counts: [[0; COLUMNS]; ROWS],
+            repeat_counts: [[0; COLUMNS]; ROWS],
+            debounce_scans: DEFAULT_DEBOUNCE_SCANS,
+            repeat: None,
+            ghosting: false,
+            wake_enabled: false,
+        }
+    }
+
+    /// Sets the number of consecutive scans a key must keep its new state to be reported.
+    ///
+    /// # Arguments
+    /// `scans` - Provides the number of scans, at least 1.
+    pub fn set_debounce(&mut self, scans: u8) {
+        debug_assert!(scans > 0);
+        self.debounce_scans = scans;
+    }
+
+    /// Enables the auto-repeat of held keys.
+    ///
+    /// # Arguments
+    /// `delay_scans` - Provides the number of scans between a press and the first repeat.
+    /// `interval_scans` - Provides the number of scans between two repeats.
+    pub fn enable_repeat(&mut self, delay_scans: u16, interval_scans: u16) {
+        debug_assert!(delay_scans > 0 && interval_scans > 0);
+
+        self.repeat = Some(KeypadRepeat {
+            delay_scans,
+            interval_scans,
+        });
+    }
+
+    /// Disables the auto-repeat of held keys.
+    pub fn disable_repeat(&mut self) {
+        self.repeat = None;
+    }
+
+    /// Checks if a key is pressed, according to the debounced state.
+    ///
+    /// # Arguments
+    /// `key` - Provides the position of the key.
+    ///
+    /// # Returns
+    /// `true` if the key is pressed.
+    /// `false` otherwise.
+    pub fn is_pressed(&self, key: KeyPosition) -> bool {
+        (self.pressed[key.row as usize] & (1 << key.column)) != 0
+    }
+
+    /// Scans the keypad and reports the changes. Should be called periodically, as debouncing
+    /// and auto-repeat are counted in scans. While waking is enabled, `scan_and_rearm` should be
+    /// called instead, as the scan raises the column interrupts.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function invoked for each event.
+    pub fn scan<F: FnMut(KeypadEvent)>(&mut self, mut handler: F) {
+        let column_mask = (1usize << COLUMNS) - 1;
+        let mut raw = [0u16; ROWS];
+
+        for (row, raw_row) in raw.iter_mut().enumerate() {
+            self.rows.select_rows(1 << row);
+            self.delay.delay_us(ROW_SETTLE_US);
+            *raw_row = (!self.columns.read() & column_mask) as u16;
+        }
+
+        self.idle_rows();
+
+        if has_ghosting(&raw) {
+            if !self.ghosting {
+                handler(KeypadEvent::Ghosting);
+            }
+
+            self.ghosting = true;
+            return;
+        }
+
+        self.ghosting = false;
+
+        for (row, raw_row) in raw.iter().enumerate() {
+            for column in 0..COLUMNS {
+                let key = KeyPosition {
+                    row: row as u8,
+                    column: column as u8,
+                };
+
+                let bit = 1 << column;
+                let was_pressed = (self.pressed[row] & bit) != 0;
+                let is_pressed = (raw_row & bit) != 0;
+
+                if is_pressed != was_pressed {
+                    self.debounce_counts[row][column] += 1;
+
+                    if self.debounce_counts[row][column] >= self.debounce_scans {
+                        self.debounce_counts[row][column] = 0;
+                        self.pressed[row] ^= bit;
+
+                        if is_pressed {
+                            self.repeat_counts[row][column] =
+                                self.repeat.map_or(0, |repeat| repeat.delay_scans);
+                            handler(KeypadEvent::Pressed(key));
+                        } else {
+                            handler(KeypadEvent::Released(key));
+                        }
+                    }
+
+                    continue;
+                }
+
+                self.debounce_counts[row][column] = 0;
+
+                if let (true, Some(repeat)) = (is_pressed, self.repeat) {
+                    let count = &mut self.repeat_counts[row][column];
+                    *count = count.saturating_sub(1);
+
+                    if *count == 0 {
+                        *count = repeat.interval_scans;
+                        handler(KeypadEvent::Repeated(key));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Extracts the row and column buses and the delay source.
+    ///
+    /// # Returns
+    /// The row bus, the column bus and the delay source.
+    pub fn extract_parts(
+        self,
+    ) -> (
+        Rows,
+        GpioSectionBus<COLUMNS, ColumnSection, GpioIn<PullUp>>,
+        Delay,
+    ) {
+        (self.rows, self.columns, self.delay)
+    }
+
+    /// Places the rows in their state between scans.
+    fn idle_rows(&mut self) {
+        let selected_mask = if self.wake_enabled {
+            (1 << ROWS) - 1
+        } else {
+            0
+        };
+
+        self.rows.select_rows(selected_mask);
+    }
+}
+
+/// The following implements waking on key presses for keypads with interrupt capable columns.
+impl<const ROWS: usize, const COLUMNS: usize, Rows, ColumnSection, Delay>
+    Keypad<ROWS, COLUMNS, Rows, ColumnSection, Delay>
+where
+    Rows: KeypadRows<ROWS>,
+    ColumnSection: PortSectionWithInterrupt<COLUMNS>,
+    Delay: DelayNs,
+{
+    /// Keeps all rows selected between scans and enables the column interrupts, so pressing any
+    /// key invokes a handler, such as to wake the application and resume scanning.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function invoked when a key is pressed between scans.
+    pub fn enable_wake(&mut self, handler: fn()) {
+        self.wake_enabled = true;
+        self.idle_rows();
+        self.delay.delay_us(ROW_SETTLE_US);
+
+        self.columns.clear_pending((1 << COLUMNS) - 1);
+        self.columns.set_interrupt_handler(handler);
+        self.columns.enable_interrupts(Edge::FallingEdge);
+    }
+
+    /// Disables the column interrupts and deselects all rows between scans.
+    pub fn disable_wake(&mut self) {
+        self.columns.disable_interrupts();
+        self.columns.clear_interrupt_handler();
+        self.wake_enabled = false;
+        self.idle_rows();
+    }
+
+    /// Scans the keypad and reports the changes. While waking is enabled, the column interrupts
+    /// are disabled during the scan, so driving the rows does not invoke the wake handler, and
+    /// enabled again once the rows are back in their state between scans.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function invoked for each event.
+    pub fn scan_and_rearm<F: FnMut(KeypadEvent)>(&mut self, handler: F) {
+        if self.wake_enabled {
+            self.columns.disable_interrupts();
+        }
+
+        self.scan(handler);
+
+        if self.wake_enabled {
+            self.delay.delay_us(ROW_SETTLE_US);
+            self.columns.clear_pending((1 << COLUMNS) - 1);
+            self.columns.enable_interrupts(Edge::FallingEdge);
+        }
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Checks if pressed keys form a rectangle in the matrix. Three corners of a rectangle make the
+/// fourth corner appear pressed, so such a scan cannot be trusted.
+///
+/// # Arguments
+/// `raw` - Provides the pressed keys of each row.
+///
+/// # Returns
+/// `true` if two rows share at least two pressed columns.
+/// `false` otherwise.
+fn has_ghosting(raw: &[u16]) -> bool {
+    for (index, first_row) in raw.iter().enumerate() {
+        for second_row in &raw[index + 1..] {
+            if (first_row & second_row).count_ones() >= 2 {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+//
+// For sealed traits.
+//
+
+mod private {
+    pub trait Sealed {}
+}
+
+impl<const ROWS: usize, Section: PortSectionX<ROWS>, OutputMode: GpioPushPullMode> private::Sealed
+    for GpioSectionBus<ROWS, Section, GpioOut<OutputMode>>
+{
+}
+impl<const ROWS: usize, Section: PortSectionX<ROWS>> private::Sealed
+    for GpioSectionBus<ROWS, Section, GpioOut<OpenCollector>>
+{
+}
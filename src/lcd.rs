@@ -0,0 +1,565 @@
+//! # LCD
+//! The `lcd` module includes structures and functions to utilize HD44780 compatible character
+//! LCDs over a 4-bit GPIO section bus.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{GpioOut, GpioPin, GpioPinOutput, GpioSectionBus, PushPull},
+    parallel::ParallelDataBus,
+    pin::{PinX, PortSectionX},
+};
+use core::fmt;
+use embedded_hal::delay::DelayNs;
+
+//
+// Consts
+//
+
+const CLEAR_DISPLAY_COMMAND: u8 = 0x01;
+const RETURN_HOME_COMMAND: u8 = 0x02;
+
+const ENTRY_MODE_COMMAND: u8 = 0x04;
+const ENTRY_INCREMENT_MASK: u8 = 0x02;
+
+const DISPLAY_CONTROL_COMMAND: u8 = 0x08;
+const DISPLAY_ON_MASK: u8 = 0x04;
+const CURSOR_ON_MASK: u8 = 0x02;
+const BLINK_ON_MASK: u8 = 0x01;
+
+const SHIFT_COMMAND: u8 = 0x10;
+const SHIFT_DISPLAY_MASK: u8 = 0x08;
+const SHIFT_RIGHT_MASK: u8 = 0x04;
+
+const FUNCTION_SET_COMMAND: u8 = 0x20;
+const FUNCTION_8_BIT_MASK: u8 = 0x10;
+const FUNCTION_2_LINES_MASK: u8 = 0x08;
+
+const SET_CGRAM_ADDRESS_COMMAND: u8 = 0x40;
+const SET_DDRAM_ADDRESS_COMMAND: u8 = 0x80;
+
+/// Busy flag, in the high nibble of the status read.
+const BUSY_FLAG_MASK: u8 = 0x80;
+
+/// Number of custom characters stored in CGRAM.
+pub const LCD_CUSTOM_CHARACTERS: u8 = 8;
+
+/// Time to complete most commands, in microseconds.
+const COMMAND_DELAY_US: u32 = 50;
+
+/// Time to complete the clear and home commands, in microseconds.
+const LONG_COMMAND_DELAY_US: u32 = 2_000;
+
+/// Time for the controller to power up before initialization, in microseconds.
+const POWER_UP_DELAY_US: u32 = 40_000;
+
+/// Time the E strobe and data setup are held, in nanoseconds.
+const ENABLE_PULSE_NS: u32 = 1_000;
+
+//
+// Traits
+//
+
+/// The optional RW line of a HD44780 LCD.
+pub trait LcdReadWrite: private::Sealed {
+    /// Drives the RW line.
+    ///
+    /// # Arguments
+    /// `read` - Provides whether the LCD is read.
+    ///
+    /// # Returns
+    /// `true` if the line is connected, so the busy flag can be read.
+    /// `false` otherwise.
+    fn set_read(&mut self, read: bool) -> bool;
+}
+
+//
+// Structures
+//
+
+/// A zero-sized structure indicating the RW line is tied low, so the LCD is only written to and
+/// commands are timed with delays.
+pub struct NoReadWrite;
+impl LcdReadWrite for NoReadWrite {
+    fn set_read(&mut self, _read: bool) -> bool {
+        false
+    }
+}
+
+impl<Pin: PinX> LcdReadWrite for GpioPin<Pin, GpioOut<PushPull>> {
+    fn set_read(&mut self, read: bool) -> bool {
+        if read {
+            self.set();
+        } else {
+            self.clear();
+        }
+
+        true
+    }
+}
+
+/// Represents the direction the display content moves in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LcdShift {
+    Left,
+    Right,
+}
+
+/// Represents a HD44780 compatible character LCD driven over a 4-bit data bus, connected to DB4
+/// to DB7 of the LCD.
+pub struct Hd44780<Section, Rs, E, Rw, Delay>
+where
+    Section: PortSectionX<4>,
+    Rs: GpioPinOutput,
+    E: GpioPinOutput,
+    Rw: LcdReadWrite,
+    Delay: DelayNs,
+{
+    /// The data bus. Only `None` while the bus is being turned around.
+    bus: Option<GpioSectionBus<4, Section, GpioOut<PushPull>>>,
+
+    /// The register select line, low for commands and high for data.
+    rs: Rs,
+
+    /// The enable strobe.
+    e: E,
+
+    /// The optional read/write line.
+    rw: Rw,
+
+    /// The delay source used to time the commands.
+    delay: Delay,
+
+    /// The number of columns of the display.
+    columns: u8,
+
+    /// The number of rows of the display.
+    rows: u8,
+
+    /// The column the next character is written to.
+    column: u8,
+
+    /// The row the next character is written to.
+    row: u8,
+
+    /// The current display control flags.
+    display_control: u8,
+}
+
+impl<Section, Rs, E, Delay> Hd44780<Section, Rs, E, NoReadWrite, Delay>
+where
+    Section: PortSectionX<4>,
+    Rs: GpioPinOutput,
+    E: GpioPinOutput,
+    Delay: DelayNs,
+{
+    /// Creates and initializes a LCD with the RW line tied low. Commands are timed with delays.
+    ///
+    /// # Arguments
+    /// `bus` - Provides the data bus connected to DB4 to DB7.
+    /// `rs` - Provides the RS pin.
+    /// `e` - Provides the E pin.
+    /// `delay` - Provides the delay source used to time the commands.
+    /// `columns` - Provides the number of columns of the display.
+    /// `rows` - Provides the number of rows of the display, from 1 to 4.
+    ///
+    /// # Returns
+    /// A cleared LCD, with the display on and the cursor hidden.
+    pub fn new(
+        bus: GpioSectionBus<4, Section, GpioOut<PushPull>>,
+        rs: Rs,
+        e: E,
+        delay: Delay,
+        columns: u8,
+        rows: u8,
+    ) -> Self {
+        Self::create(bus, rs, e, NoReadWrite, delay, columns, rows)
+    }
+}
+
+impl<Section, Rs, E, RwPin, Delay> Hd44780<Section, Rs, E, GpioPin<RwPin, GpioOut<PushPull>>, Delay>
+where
+    Section: PortSectionX<4>,
+    Rs: GpioPinOutput,
+    E: GpioPinOutput,
+    RwPin: PinX,
+    Delay: DelayNs,
+{
+    /// Creates and initializes a LCD with a RW line. Commands wait for the busy flag to clear.
+    ///
+    /// # Arguments
+    /// `bus` - Provides the data bus connected to DB4 to DB7.
+    /// `rs` - Provides the RS pin.
+    /// `e` - Provides the E pin.
+    /// `rw` - Provides the RW pin.
+    /// `delay` - Provides the delay source used during initialization.
+    /// `columns` - Provides the number of columns of the display.
+    /// `rows` - Provides the number of rows of the display, from 1 to 4.
+    ///
+    /// # Returns
+    /// A cleared LCD, with the display on and the cursor hidden.
+    pub fn new_with_busy_flag(
+        bus: GpioSectionBus<4, Section, GpioOut<PushPull>>,
+        rs: Rs,
+        e: E,
+        rw: GpioPin<RwPin, GpioOut<PushPull>>,
+        delay: Delay,
+        columns: u8,
+        rows: u8,
+    ) -> Self {
+        Self::create(bus, rs, e, rw, delay, columns, rows)
+    }
+}
+
+impl<Section, Rs, E, Rw, Delay> Hd44780<Section, Rs, E, Rw, Delay>
+where
+    Section: PortSectionX<4>,
+    Rs: GpioPinOutput,
+    E: GpioPinOutput,
+    Rw: LcdReadWrite,
+    Delay: DelayNs,
+{
+    /// Clears the display and moves the cursor to the top-left position.
+    pub fn clear(&mut self) {
+        self.write_command(CLEAR_DISPLAY_COMMAND, LONG_COMMAND_DELAY_US);
+        self.column = 0;
+        self.row = 0;
+    }
+
+    /// Moves the cursor to the top-left position and undoes any display shift.
+    pub fn home(&mut self) {
+        self.write_command(RETURN_HOME_COMMAND, LONG_COMMAND_DELAY_US);
+        self.column = 0;
+        self.row = 0;
+    }
+
+    /// Moves the cursor.
+    ///
+    /// # Arguments
+    /// `column` - Provides the column, starting from 0.
+    /// `row` - Provides the row, starting from 0.
+    pub fn set_cursor(&mut self, column: u8, row: u8) {
+        debug_assert!(column < self.columns && row < self.rows);
+
+        // Rows 2 and 3 continue rows 0 and 1 in DDRAM.
+
+        let row_address = match row {
+            0 => 0x00,
+            1 => 0x40,
+            2 => self.columns,
+            _ => 0x40 + self.columns,
+        };
+
+        self.write_command(
+            SET_DDRAM_ADDRESS_COMMAND | (row_address + column),
+            COMMAND_DELAY_US,
+        );
+        self.column = column;
+        self.row = row;
+    }
+
+    /// Turns the display on or off. The content is kept while the display is off.
+    ///
+    /// # Arguments
+    /// `on` - Provides whether the display is on.
+    pub fn set_display_on(&mut self, on: bool) {
+        self.update_display_control(DISPLAY_ON_MASK, on);
+    }
+
+    /// Shows or hides the underline cursor.
+    ///
+    /// # Arguments
+    /// `visible` - Provides whether the cursor is shown.
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.update_display_control(CURSOR_ON_MASK, visible);
+    }
+
+    /// Enables or disables the blinking block cursor.
+    ///
+    /// # Arguments
+    /// `blink` - Provides whether the cursor blinks.
+    pub fn set_cursor_blink(&mut self, blink: bool) {
+        self.update_display_control(BLINK_ON_MASK, blink);
+    }
+
+    /// Shifts the content of the display by one position, without changing DDRAM.
+    ///
+    /// # Arguments
+    /// `direction` - Provides the direction of the shift.
+    pub fn shift_display(&mut self, direction: LcdShift) {
+        let direction = match direction {
+            LcdShift::Left => 0,
+            LcdShift::Right => SHIFT_RIGHT_MASK,
+        };
+
+        self.write_command(
+            SHIFT_COMMAND | SHIFT_DISPLAY_MASK | direction,
+            COMMAND_DELAY_US,
+        );
+    }
+
+    /// Defines a custom character, displayed by writing its index. The cursor is moved to the
+    /// top-left position.
+    ///
+    /// # Arguments
+    /// `index` - Provides the index of the character, below `LCD_CUSTOM_CHARACTERS`.
+    /// `pattern` - Provides the rows of the 5x8 character, top row first, in the 5 least
+    ///   significant bits.
+    pub fn create_char(&mut self, index: u8, pattern: &[u8; 8]) {
+        debug_assert!(index < LCD_CUSTOM_CHARACTERS);
+
+        self.write_command(SET_CGRAM_ADDRESS_COMMAND | (index << 3), COMMAND_DELAY_US);
+
+        for row in pattern {
+            self.write_data(*row & 0x1F);
+        }
+
+        self.set_cursor(0, 0);
+    }
+
+    /// Writes a raw character code at the cursor, and moves the cursor to the next position,
+    /// wrapping to the next row.
+    ///
+    /// # Arguments
+    /// `code` - Provides the character code.
+    pub fn write_char_code(&mut self, code: u8) {
+        self.write_data(code);
+        self.column += 1;
+
+        if self.column >= self.columns {
+            let row = (self.row + 1) % self.rows;
+            self.set_cursor(0, row);
+        }
+    }
+
+    /// Writes a string at the cursor. A line feed moves the cursor to the next row, and
+    /// characters missing from the LCD character set are replaced with `?`.
+    ///
+    /// # Arguments
+    /// `text` - Provides the string.
+    pub fn print(&mut self, text: &str) {
+        for character in text.chars() {
+            match character {
+                '\n' => {
+                    let row = (self.row + 1) % self.rows;
+                    self.set_cursor(0, row);
+                }
+
+                ' '..='}' => self.write_char_code(character as u8),
+                _ => self.write_char_code(b'?'),
+            }
+        }
+    }
+
+    /// Extracts the data bus, the control pins and the delay source.
+    ///
+    /// # Returns
+    /// The data bus, the RS, E and RW pins, and the delay source.
+    pub fn extract_parts(
+        self,
+    ) -> (
+        GpioSectionBus<4, Section, GpioOut<PushPull>>,
+        Rs,
+        E,
+        Rw,
+        Delay,
+    ) {
+        (self.bus.unwrap(), self.rs, self.e, self.rw, self.delay)
+    }
+
+    /// Creates and initializes a LCD.
+    ///
+    /// # Arguments
+    /// `bus` - Provides the data bus connected to DB4 to DB7.
+    /// `rs` - Provides the RS pin.
+    /// `e` - Provides the E pin.
+    /// `rw` - Provides the RW line.
+    /// `delay` - Provides the delay source used to time the commands.
+    /// `columns` - Provides the number of columns of the display.
+    /// `rows` - Provides the number of rows of the display.
+    ///
+    /// # Returns
+    /// A cleared LCD, with the display on and the cursor hidden.
+    fn create(
+        bus: GpioSectionBus<4, Section, GpioOut<PushPull>>,
+        mut rs: Rs,
+        mut e: E,
+        mut rw: Rw,
+        delay: Delay,
+        columns: u8,
+        rows: u8,
+    ) -> Self {
+        debug_assert!((1..=4).contains(&rows));
+
+        rs.clear();
+        e.clear();
+        rw.set_read(false);
+
+        let mut lcd = Hd44780 {
+            bus: Some(bus),
+            rs,
+            e,
+            rw,
+            delay,
+            columns,
+            rows,
+            column: 0,
+            row: 0,
+            display_control: DISPLAY_CONTROL_COMMAND | DISPLAY_ON_MASK,
+        };
+
+        // The controller may be in 8-bit mode, or halfway through a 4-bit transfer. Three 8-bit
+        // function sets bring it to a known state before switching to 4-bit mode.
+
+        lcd.delay.delay_us(POWER_UP_DELAY_US);
+        lcd.write_nibble(FUNCTION_SET_COMMAND | FUNCTION_8_BIT_MASK);
+        lcd.delay.delay_us(4_100);
+        lcd.write_nibble(FUNCTION_SET_COMMAND | FUNCTION_8_BIT_MASK);
+        lcd.delay.delay_us(100);
+        lcd.write_nibble(FUNCTION_SET_COMMAND | FUNCTION_8_BIT_MASK);
+        lcd.delay.delay_us(100);
+        lcd.write_nibble(FUNCTION_SET_COMMAND);
+        lcd.delay.delay_us(COMMAND_DELAY_US);
+
+        let lines = if rows > 1 { FUNCTION_2_LINES_MASK } else { 0 };
+        lcd.write_command(FUNCTION_SET_COMMAND | lines, COMMAND_DELAY_US);
+        lcd.write_command(DISPLAY_CONTROL_COMMAND, COMMAND_DELAY_US);
+        lcd.clear();
+        lcd.write_command(ENTRY_MODE_COMMAND | ENTRY_INCREMENT_MASK, COMMAND_DELAY_US);
+        lcd.write_command(lcd.display_control, COMMAND_DELAY_US);
+
+        lcd
+    }
+
+    /// Sets or clears a display control flag.
+    ///
+    /// # Arguments
+    /// `mask` - Provides the flag.
+    /// `enabled` - Provides whether the flag is set.
+    fn update_display_control(&mut self, mask: u8, enabled: bool) {
+        if enabled {
+            self.display_control |= mask;
+        } else {
+            self.display_control &= !mask;
+        }
+
+        self.write_command(self.display_control, COMMAND_DELAY_US);
+    }
+
+    /// Writes a command and waits for its completion.
+    ///
+    /// # Arguments
+    /// `command` - Provides the command.
+    /// `duration_us` - Provides the execution time of the command, when the busy flag cannot be
+    ///   read.
+    fn write_command(&mut self, command: u8, duration_us: u32) {
+        self.rs.clear();
+        self.write_byte(command);
+        self.wait_ready(duration_us);
+    }
+
+    /// Writes a byte to CGRAM or DDRAM and waits for its completion.
+    ///
+    /// # Arguments
+    /// `data` - Provides the byte.
+    fn write_data(&mut self, data: u8) {
+        self.rs.set();
+        self.write_byte(data);
+        self.wait_ready(COMMAND_DELAY_US);
+    }
+
+    /// Writes a byte as two nibbles, high nibble first.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the byte.
+    fn write_byte(&mut self, byte: u8) {
+        self.write_nibble(byte);
+        self.write_nibble(byte << 4);
+    }
+
+    /// Writes the high nibble of a byte.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the byte.
+    fn write_nibble(&mut self, byte: u8) {
+        if let Some(bus) = &mut self.bus {
+            bus.write_value((byte >> 4) as usize);
+        }
+
+        self.delay.delay_ns(ENABLE_PULSE_NS);
+        self.e.set();
+        self.delay.delay_ns(ENABLE_PULSE_NS);
+        self.e.clear();
+        self.delay.delay_ns(ENABLE_PULSE_NS);
+    }
+
+    /// Waits for the completion of the last command, polling the busy flag when RW is connected.
+    ///
+    /// # Arguments
+    /// `duration_us` - Provides the execution time of the command, when the busy flag cannot be
+    ///   read.
+    fn wait_ready(&mut self, duration_us: u32) {
+        self.rs.clear();
+        if !self.rw.set_read(true) {
+            self.delay.delay_us(duration_us);
+            return;
+        }
+
+        // The LCD drives the bus while reading, so the bus is only driven again after RW is
+        // back low.
+
+        let mut input = self.bus.take().map(ParallelDataBus::into_input);
+
+        // A disconnected LCD must not hang the application, so polling stops once the command
+        // had ample time to complete.
+
+        for _ in 0..duration_us {
+            let mut status = 0;
+
+            for _ in 0..2 {
+                self.e.set();
+                self.delay.delay_ns(ENABLE_PULSE_NS);
+                if let Some(input) = &input {
+                    status = (status << 4)
+                        | <GpioSectionBus<4, Section, GpioOut<PushPull>>>::read_value(input) as u8;
+                }
+                self.e.clear();
+                self.delay.delay_ns(ENABLE_PULSE_NS);
+            }
+
+            if (status & BUSY_FLAG_MASK) == 0 {
+                break;
+            }
+        }
+
+        self.rw.set_read(false);
+        self.bus = input.take().map(ParallelDataBus::from_input);
+    }
+}
+
+impl<Section, Rs, E, Rw, Delay> fmt::Write for Hd44780<Section, Rs, E, Rw, Delay>
+where
+    Section: PortSectionX<4>,
+    Rs: GpioPinOutput,
+    E: GpioPinOutput,
+    Rw: LcdReadWrite,
+    Delay: DelayNs,
+{
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.print(text);
+        Ok(())
+    }
+}
+
+//
+// For sealed traits.
+//
+
+mod private {
+    pub trait Sealed {}
+}
+
+impl private::Sealed for NoReadWrite {}
+impl<Pin: PinX> private::Sealed for GpioPin<Pin, GpioOut<PushPull>> {}
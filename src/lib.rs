@@ -5,11 +5,21 @@
 #![allow(dead_code)]
 
 mod registers;
+mod ringbuffer;
 
+pub mod clock;
+pub mod eusci;
 pub mod gpio;
+pub mod i2c;
 pub mod interrupt;
+pub mod keypad;
+pub mod lcd;
+pub mod onewire;
+pub mod parallel;
 pub mod pin;
+pub mod quadrature;
 pub mod spi;
+pub mod uart;
 pub mod watchdog;
 
 pub enum Edge {
@@ -0,0 +1,616 @@
+//! # OneWire
+//! The `onewire` module includes structures and functions to utilize the Dallas/Maxim 1-Wire
+//! protocol over an open-collector GPIO pin.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{GpioOut, GpioPin, GpioPinInput, GpioPinOutput, OpenCollector},
+    interrupt::single_proc_critical_section,
+    pin::PinX,
+};
+use embedded_hal::delay::DelayNs;
+
+//
+// Consts
+//
+
+/// Reads the ROM code of the only device on the bus.
+const READ_ROM_COMMAND: u8 = 0x33;
+
+/// Addresses a single device by its ROM code.
+const MATCH_ROM_COMMAND: u8 = 0x55;
+
+/// Addresses every device on the bus.
+const SKIP_ROM_COMMAND: u8 = 0xCC;
+
+/// Starts a search through the ROM codes of every device.
+const SEARCH_ROM_COMMAND: u8 = 0xF0;
+
+/// Starts a search through the ROM codes of devices with an alarm condition.
+const ALARM_SEARCH_COMMAND: u8 = 0xEC;
+
+/// Addresses every device on the bus and switches them to overdrive speed.
+const OVERDRIVE_SKIP_ROM_COMMAND: u8 = 0x3C;
+
+/// Addresses a single device by its ROM code and switches it to overdrive speed.
+const OVERDRIVE_MATCH_ROM_COMMAND: u8 = 0x69;
+
+/// Slot timings at standard speed, from Maxim application note 126.
+const STANDARD_TIMING: OneWireTiming = OneWireTiming {
+    a: 6_000,
+    b: 64_000,
+    c: 60_000,
+    d: 10_000,
+    e: 9_000,
+    f: 55_000,
+    g: 0,
+    h: 480_000,
+    i: 70_000,
+    j: 410_000,
+};
+
+/// Slot timings at overdrive speed, from Maxim application note 126.
+const OVERDRIVE_TIMING: OneWireTiming = OneWireTiming {
+    a: 1_000,
+    b: 7_500,
+    c: 7_500,
+    d: 2_500,
+    e: 1_000,
+    f: 7_000,
+    g: 2_500,
+    h: 70_000,
+    i: 8_500,
+    j: 40_000,
+};
+
+//
+// Structures
+//
+
+/// Represents the speed of the 1-Wire bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OneWireSpeed {
+    Standard,
+    Overdrive,
+}
+
+/// Represents an error that aborted a 1-Wire operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OneWireError {
+    /// No device answered the reset pulse.
+    NoPresence,
+
+    /// The bus was held low before the reset pulse.
+    BusStuckLow,
+
+    /// The CRC of received data did not match.
+    CrcMismatch,
+}
+
+/// Represents the 64-bit ROM code that uniquely identifies a 1-Wire device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OneWireRom(pub [u8; 8]);
+
+impl OneWireRom {
+    /// Gets the family code of the device.
+    ///
+    /// # Returns
+    /// The family code, in the least significant byte of the ROM code.
+    pub fn get_family_code(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// Checks the CRC of the ROM code.
+    ///
+    /// # Returns
+    /// `true` if the CRC in the most significant byte matches the other bytes.
+    /// `false` otherwise.
+    pub fn is_valid(&self) -> bool {
+        crc8(&self.0) == 0
+    }
+}
+
+/// Represents the progress of a search through the ROM codes of the devices on the bus.
+pub struct OneWireSearch {
+    /// The ROM code found by the previous search step.
+    rom: [u8; 8],
+
+    /// The bit index of the last discrepancy where the `0` branch was taken, from 1 to 64. `0`
+    /// when no such discrepancy remains.
+    last_discrepancy: u8,
+
+    /// Whether the last device was found.
+    done: bool,
+
+    /// Whether only devices with an alarm condition are searched.
+    alarm_only: bool,
+}
+
+impl OneWireSearch {
+    /// Creates a new search through the ROM codes of every device.
+    ///
+    /// # Returns
+    /// A new search.
+    pub fn new() -> Self {
+        OneWireSearch {
+            rom: [0; 8],
+            last_discrepancy: 0,
+            done: false,
+            alarm_only: false,
+        }
+    }
+
+    /// Creates a new search through the ROM codes of devices with an alarm condition.
+    ///
+    /// # Returns
+    /// A new search.
+    pub fn new_alarm() -> Self {
+        OneWireSearch {
+            alarm_only: true,
+            ..Self::new()
+        }
+    }
+}
+
+impl Default for OneWireSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Represents the durations of the 1-Wire slots, in nanoseconds, named after Maxim application
+/// note 126.
+struct OneWireTiming {
+    /// Low time of a write 1 and read slot.
+    a: u32,
+
+    /// Recovery time after a write 1 slot.
+    b: u32,
+
+    /// Low time of a write 0 slot.
+    c: u32,
+
+    /// Recovery time after a write 0 slot.
+    d: u32,
+
+    /// Time between releasing the bus and sampling in a read slot.
+    e: u32,
+
+    /// Recovery time after a read slot.
+    f: u32,
+
+    /// Time before a reset pulse.
+    g: u32,
+
+    /// Low time of a reset pulse.
+    h: u32,
+
+    /// Time between releasing the bus and sampling the presence pulse.
+    i: u32,
+
+    /// Recovery time after the presence pulse.
+    j: u32,
+}
+
+/// Represents a 1-Wire bus master driving an open-collector GPIO pin.
+pub struct OneWire<Pin: PinX, Delay: DelayNs> {
+    /// The data line.
+    pin: GpioPin<Pin, GpioOut<OpenCollector>>,
+
+    /// The delay source used to time the slots.
+    delay: Delay,
+
+    /// The time spent toggling and reading the pin, subtracted from every delay.
+    overhead_ns: u32,
+
+    /// The current bus speed.
+    speed: OneWireSpeed,
+}
+
+impl<Pin: PinX, Delay: DelayNs> OneWire<Pin, Delay> {
+    /// Creates a new 1-Wire bus master at standard speed. The data line is released.
+    ///
+    /// # Arguments
+    /// `pin` - Provides the data line pin.
+    /// `delay` - Provides the delay source used to time the slots.
+    ///
+    /// # Returns
+    /// A new 1-Wire bus master.
+    pub fn new(mut pin: GpioPin<Pin, GpioOut<OpenCollector>>, delay: Delay) -> Self {
+        pin.set();
+
+        OneWire {
+            pin,
+            delay,
+            overhead_ns: 0,
+            speed: OneWireSpeed::Standard,
+        }
+    }
+
+    /// Calibrates the delays, to compensate for the time spent toggling and reading the pin.
+    /// Required to meet overdrive timings on slower system clocks.
+    ///
+    /// # Arguments
+    /// `overhead_ns` - Provides the time spent in a pin access, subtracted from every delay.
+    pub fn set_overhead(&mut self, overhead_ns: u32) {
+        self.overhead_ns = overhead_ns;
+    }
+
+    /// Gets the current bus speed.
+    ///
+    /// # Returns
+    /// The bus speed.
+    pub fn get_speed(&self) -> OneWireSpeed {
+        self.speed
+    }
+
+    /// Generates a reset pulse and detects the presence pulse of the devices.
+    ///
+    /// # Returns
+    /// `Ok(())` if at least one device answered.
+    /// `Err(OneWireError)` otherwise.
+    pub fn reset(&mut self) -> Result<(), OneWireError> {
+        let timing = self.get_timing();
+
+        if !self.pin.read() {
+            return Err(OneWireError::BusStuckLow);
+        }
+
+        let mut presence = false;
+
+        self.wait(timing.g);
+        self.pin.clear();
+        self.wait(timing.h);
+
+        single_proc_critical_section(|_| {
+            self.pin.set();
+            self.wait(timing.i);
+            presence = !self.pin.read();
+        });
+
+        self.wait(timing.j);
+
+        if presence {
+            Ok(())
+        } else {
+            Err(OneWireError::NoPresence)
+        }
+    }
+
+    /// Writes a single bit.
+    ///
+    /// # Arguments
+    /// `bit` - Provides the bit to write.
+    pub fn write_bit(&mut self, bit: bool) {
+        let timing = self.get_timing();
+
+        // The low time decides the bit, so the slot cannot be stretched by interrupts.
+
+        single_proc_critical_section(|_| {
+            self.pin.clear();
+            self.wait(if bit { timing.a } else { timing.c });
+            self.pin.set();
+        });
+
+        self.wait(if bit { timing.b } else { timing.d });
+    }
+
+    /// Reads a single bit.
+    ///
+    /// # Returns
+    /// The read bit.
+    pub fn read_bit(&mut self) -> bool {
+        let timing = self.get_timing();
+        let mut bit = false;
+
+        single_proc_critical_section(|_| {
+            self.pin.clear();
+            self.wait(timing.a);
+            self.pin.set();
+            self.wait(timing.e);
+            bit = self.pin.read();
+        });
+
+        self.wait(timing.f);
+        bit
+    }
+
+    /// Writes a byte, least significant bit first.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the byte to write.
+    pub fn write_byte(&mut self, byte: u8) {
+        for bit in 0..8 {
+            self.write_bit((byte >> bit) & 1 != 0);
+        }
+    }
+
+    /// Reads a byte, least significant bit first.
+    ///
+    /// # Returns
+    /// The read byte.
+    pub fn read_byte(&mut self) -> u8 {
+        let mut byte = 0;
+
+        for bit in 0..8 {
+            if self.read_bit() {
+                byte |= 1 << bit;
+            }
+        }
+
+        byte
+    }
+
+    /// Writes bytes.
+    ///
+    /// # Arguments
+    /// `bytes` - Provides the bytes to write.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write_byte(*byte);
+        }
+    }
+
+    /// Reads bytes.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the read bytes.
+    pub fn read_bytes(&mut self, buffer: &mut [u8]) {
+        for byte in buffer.iter_mut() {
+            *byte = self.read_byte();
+        }
+    }
+
+    /// Reads bytes followed by their CRC8, and checks the CRC.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the read bytes, without the CRC.
+    ///
+    /// # Returns
+    /// `Ok(())` if the CRC matched.
+    /// `Err(OneWireError)` otherwise.
+    pub fn read_bytes_checked(&mut self, buffer: &mut [u8]) -> Result<(), OneWireError> {
+        self.read_bytes(buffer);
+
+        if crc8(buffer) == self.read_byte() {
+            Ok(())
+        } else {
+            Err(OneWireError::CrcMismatch)
+        }
+    }
+
+    /// Resets the bus and reads the ROM code of the only device on the bus.
+    ///
+    /// # Returns
+    /// `Ok(OneWireRom)` with the ROM code of the device.
+    /// `Err(OneWireError)` if no device answered, or if several devices answered.
+    pub fn read_rom(&mut self) -> Result<OneWireRom, OneWireError> {
+        self.reset()?;
+        self.write_byte(READ_ROM_COMMAND);
+
+        let mut rom = OneWireRom([0; 8]);
+        self.read_bytes(&mut rom.0);
+
+        if rom.is_valid() {
+            Ok(rom)
+        } else {
+            Err(OneWireError::CrcMismatch)
+        }
+    }
+
+    /// Resets the bus and addresses a single device. The next bytes written are function
+    /// commands for that device.
+    ///
+    /// # Arguments
+    /// `rom` - Provides the ROM code of the device.
+    ///
+    /// # Returns
+    /// `Ok(())` if at least one device answered the reset.
+    /// `Err(OneWireError)` otherwise.
+    pub fn select(&mut self, rom: &OneWireRom) -> Result<(), OneWireError> {
+        self.reset()?;
+        self.write_byte(MATCH_ROM_COMMAND);
+        self.write_bytes(&rom.0);
+        Ok(())
+    }
+
+    /// Resets the bus and addresses every device. The next bytes written are function commands
+    /// for all devices.
+    ///
+    /// # Returns
+    /// `Ok(())` if at least one device answered the reset.
+    /// `Err(OneWireError)` otherwise.
+    pub fn skip_rom(&mut self) -> Result<(), OneWireError> {
+        self.reset()?;
+        self.write_byte(SKIP_ROM_COMMAND);
+        Ok(())
+    }
+
+    /// Finds the next device of a search through the ROM codes of the devices on the bus.
+    ///
+    /// # Arguments
+    /// `search` - Provides the progress of the search.
+    ///
+    /// # Returns
+    /// `Ok(Some(OneWireRom))` with the ROM code of the next device.
+    /// `Ok(None)` if every device was found.
+    /// `Err(OneWireError)` if no device answered, or if the search was disturbed.
+    pub fn search(
+        &mut self,
+        search: &mut OneWireSearch,
+    ) -> Result<Option<OneWireRom>, OneWireError> {
+        if search.done {
+            return Ok(None);
+        }
+
+        self.reset()?;
+        self.write_byte(if search.alarm_only {
+            ALARM_SEARCH_COMMAND
+        } else {
+            SEARCH_ROM_COMMAND
+        });
+
+        let mut last_zero_discrepancy = 0;
+
+        for bit_number in 1..=64u8 {
+            let byte_index = ((bit_number - 1) / 8) as usize;
+            let bit_mask = 1 << ((bit_number - 1) % 8);
+
+            // Every device sends its bit, then the complement of its bit. Both reading `1` means
+            // no device took part in this step.
+
+            let bit = self.read_bit();
+            let complement = self.read_bit();
+
+            let direction = match (bit, complement) {
+                (true, true) => {
+                    search.last_discrepancy = 0;
+                    search.done = true;
+                    return Ok(None);
+                }
+
+                (false, true) => false,
+                (true, false) => true,
+
+                // Devices disagree. Follow the previous path before the last discrepancy, take
+                // the `1` branch at it, and start with the `0` branch past it.
+                (false, false) => {
+                    let direction = if bit_number < search.last_discrepancy {
+                        (search.rom[byte_index] & bit_mask) != 0
+                    } else {
+                        bit_number == search.last_discrepancy
+                    };
+
+                    if !direction {
+                        last_zero_discrepancy = bit_number;
+                    }
+
+                    direction
+                }
+            };
+
+            if direction {
+                search.rom[byte_index] |= bit_mask;
+            } else {
+                search.rom[byte_index] &= !bit_mask;
+            }
+
+            self.write_bit(direction);
+        }
+
+        search.last_discrepancy = last_zero_discrepancy;
+        search.done = last_zero_discrepancy == 0;
+
+        let rom = OneWireRom(search.rom);
+        if rom.is_valid() {
+            Ok(Some(rom))
+        } else {
+            Err(OneWireError::CrcMismatch)
+        }
+    }
+
+    /// Resets the bus at standard speed and switches every device supporting it to overdrive
+    /// speed.
+    ///
+    /// # Returns
+    /// `Ok(())` if at least one device answered the reset.
+    /// `Err(OneWireError)` otherwise.
+    pub fn enter_overdrive(&mut self) -> Result<(), OneWireError> {
+        self.speed = OneWireSpeed::Standard;
+        self.reset()?;
+        self.write_byte(OVERDRIVE_SKIP_ROM_COMMAND);
+        self.speed = OneWireSpeed::Overdrive;
+        Ok(())
+    }
+
+    /// Resets the bus at standard speed, addresses a single device and switches it to overdrive
+    /// speed.
+    ///
+    /// # Arguments
+    /// `rom` - Provides the ROM code of the device.
+    ///
+    /// # Returns
+    /// `Ok(())` if at least one device answered the reset.
+    /// `Err(OneWireError)` otherwise.
+    pub fn enter_overdrive_select(&mut self, rom: &OneWireRom) -> Result<(), OneWireError> {
+        self.speed = OneWireSpeed::Standard;
+        self.reset()?;
+        self.write_byte(OVERDRIVE_MATCH_ROM_COMMAND);
+        self.speed = OneWireSpeed::Overdrive;
+        self.write_bytes(&rom.0);
+        Ok(())
+    }
+
+    /// Returns every device to standard speed with a standard speed reset pulse.
+    ///
+    /// # Returns
+    /// `Ok(())` if at least one device answered the reset.
+    /// `Err(OneWireError)` otherwise.
+    pub fn exit_overdrive(&mut self) -> Result<(), OneWireError> {
+        self.speed = OneWireSpeed::Standard;
+        self.reset()
+    }
+
+    /// Extracts the pin and the delay source.
+    ///
+    /// # Returns
+    /// The data line pin and the delay source.
+    pub fn extract_parts(self) -> (GpioPin<Pin, GpioOut<OpenCollector>>, Delay) {
+        (self.pin, self.delay)
+    }
+
+    /// Gets the slot timings of the current bus speed.
+    ///
+    /// # Returns
+    /// The slot timings.
+    fn get_timing(&self) -> &'static OneWireTiming {
+        match self.speed {
+            OneWireSpeed::Standard => &STANDARD_TIMING,
+            OneWireSpeed::Overdrive => &OVERDRIVE_TIMING,
+        }
+    }
+
+    /// Waits for a slot duration, compensated for the pin access overhead.
+    ///
+    /// # Arguments
+    /// `duration_ns` - Provides the duration to wait.
+    fn wait(&mut self, duration_ns: u32) {
+        let duration_ns = duration_ns.saturating_sub(self.overhead_ns);
+        if duration_ns > 0 {
+            self.delay.delay_ns(duration_ns);
+        }
+    }
+}
+
+//
+// Public functions.
+//
+
+/// Computes the Dallas/Maxim CRC8 of bytes. Including a received CRC in the bytes yields `0` when
+/// the CRC matches.
+///
+/// # Arguments
+/// `bytes` - Provides the bytes.
+///
+/// # Returns
+/// The CRC8.
+pub fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc = 0;
+
+    for byte in bytes {
+        let mut byte = *byte;
+        for _ in 0..8 {
+            let mix = (crc ^ byte) & 0x01;
+            crc >>= 1;
+            if mix != 0 {
+                crc ^= 0x8C;
+            }
+            byte >>= 1;
+        }
+    }
+
+    crc
+}
@@ -0,0 +1,510 @@
+//! # Parallel
+//! The `parallel` module includes structures and functions to utilize 8080 and 6800 style parallel
+//! bus interfaces, as found on display controllers, over a GPIO bus and GPIO control pins.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{
+        GpioBusInput, GpioBusOutput, GpioIn, GpioOut, GpioPinOutput, GpioPortBus, GpioSectionBus,
+        HighImpedance, PushPull,
+    },
+    pin::{PortSectionX, PortX},
+};
+use embedded_hal::delay::DelayNs;
+
+//
+// Traits
+//
+
+/// A GPIO Bus that can carry the data of a parallel interface, and be turned around for reads.
+pub trait ParallelDataBus: private::Sealed {
+    /// The same bus configured as a high-impedance input.
+    type Input;
+
+    /// Drives a value on the bus.
+    ///
+    /// # Arguments
+    /// `value` - Provides the value to drive.
+    fn write_value(&mut self, value: usize);
+
+    /// Reads a value from the bus configured as an input.
+    ///
+    /// # Arguments
+    /// `input` - Provides the bus configured as an input.
+    ///
+    /// # Returns
+    /// The value on the bus.
+    fn read_value(input: &Self::Input) -> usize;
+
+    /// Stops driving the bus.
+    ///
+    /// # Returns
+    /// The bus configured as an input.
+    fn into_input(self) -> Self::Input;
+
+    /// Drives the bus again after a read.
+    ///
+    /// # Arguments
+    /// `input` - Provides the bus configured as an input.
+    ///
+    /// # Returns
+    /// The bus configured as an output.
+    fn from_input(input: Self::Input) -> Self;
+}
+
+/// The timing of the control lines of a parallel interface.
+pub trait ParallelProtocol: private::Sealed {
+    /// Places the WR and RD lines in their idle levels.
+    ///
+    /// # Arguments
+    /// `wr` - Provides the WR line.
+    /// `rd` - Provides the RD line.
+    fn idle<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd);
+
+    /// Starts a write cycle, once the data is driven on the bus.
+    ///
+    /// # Arguments
+    /// `wr` - Provides the WR line.
+    /// `rd` - Provides the RD line.
+    fn begin_write<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd);
+
+    /// Ends a write cycle, latching the data in the device.
+    ///
+    /// # Arguments
+    /// `wr` - Provides the WR line.
+    /// `rd` - Provides the RD line.
+    fn end_write<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd);
+
+    /// Starts a read cycle, once the bus is no longer driven.
+    ///
+    /// # Arguments
+    /// `wr` - Provides the WR line.
+    /// `rd` - Provides the RD line.
+    fn begin_read<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd);
+
+    /// Ends a read cycle, once the data has been sampled.
+    ///
+    /// # Arguments
+    /// `wr` - Provides the WR line.
+    /// `rd` - Provides the RD line.
+    fn end_read<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd);
+}
+
+/// A word transferred over a parallel interface.
+pub trait ParallelWord: Copy + private::Sealed {
+    /// Converts the word into a bus value.
+    ///
+    /// # Returns
+    /// The bus value.
+    fn into_bus_value(self) -> usize;
+
+    /// Converts a bus value into a word, truncating it if needed.
+    ///
+    /// # Arguments
+    /// `value` - Provides the bus value.
+    ///
+    /// # Returns
+    /// The word.
+    fn from_bus_value(value: usize) -> Self;
+}
+
+//
+// Structures
+//
+
+/// A zero-sized typestate indicating the Intel 8080 protocol. WR and RD are separate active low
+/// strobes, and data is latched on the rising edge of WR.
+pub struct Intel8080;
+impl ParallelProtocol for Intel8080 {
+    fn idle<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd) {
+        wr.set();
+        rd.set();
+    }
+
+    fn begin_write<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, _rd: &mut Rd) {
+        wr.clear();
+    }
+
+    fn end_write<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, _rd: &mut Rd) {
+        wr.set();
+    }
+
+    fn begin_read<Wr: GpioPinOutput, Rd: GpioPinOutput>(_wr: &mut Wr, rd: &mut Rd) {
+        rd.clear();
+    }
+
+    fn end_read<Wr: GpioPinOutput, Rd: GpioPinOutput>(_wr: &mut Wr, rd: &mut Rd) {
+        rd.set();
+    }
+}
+
+/// A zero-sized typestate indicating the Motorola 6800 protocol. The WR pin carries the active
+/// high E strobe and the RD pin carries the R/W direction, and data is latched on the falling
+/// edge of E.
+pub struct Motorola6800;
+impl ParallelProtocol for Motorola6800 {
+    fn idle<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd) {
+        wr.clear();
+        rd.clear();
+    }
+
+    fn begin_write<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd) {
+        rd.clear();
+        wr.set();
+    }
+
+    fn end_write<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, _rd: &mut Rd) {
+        wr.clear();
+    }
+
+    fn begin_read<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd) {
+        rd.set();
+        wr.set();
+    }
+
+    fn end_read<Wr: GpioPinOutput, Rd: GpioPinOutput>(wr: &mut Wr, rd: &mut Rd) {
+        wr.clear();
+        rd.clear();
+    }
+}
+
+impl ParallelWord for u8 {
+    fn into_bus_value(self) -> usize {
+        self as usize
+    }
+
+    fn from_bus_value(value: usize) -> Self {
+        value as u8
+    }
+}
+
+impl ParallelWord for u16 {
+    fn into_bus_value(self) -> usize {
+        self as usize
+    }
+
+    fn from_bus_value(value: usize) -> Self {
+        value as u16
+    }
+}
+
+impl<const SIZE: usize, Section: PortSectionX<SIZE>> ParallelDataBus
+    for GpioSectionBus<SIZE, Section, GpioOut<PushPull>>
+{
+    type Input = GpioSectionBus<SIZE, Section, GpioIn<HighImpedance>>;
+
+    fn write_value(&mut self, value: usize) {
+        self.write(value);
+    }
+
+    fn read_value(input: &Self::Input) -> usize {
+        input.read()
+    }
+
+    fn into_input(self) -> Self::Input {
+        self.to_input_highz()
+    }
+
+    fn from_input(input: Self::Input) -> Self {
+        input.to_output_pushpull()
+    }
+}
+
+impl<Port: PortX> ParallelDataBus for GpioPortBus<Port, GpioOut<PushPull>> {
+    type Input = GpioPortBus<Port, GpioIn<HighImpedance>>;
+
+    fn write_value(&mut self, value: usize) {
+        self.write(value);
+    }
+
+    fn read_value(input: &Self::Input) -> usize {
+        input.read()
+    }
+
+    fn into_input(self) -> Self::Input {
+        self.to_input_highz()
+    }
+
+    fn from_input(input: Self::Input) -> Self {
+        input.to_output_pushpull()
+    }
+}
+
+/// Represents the direction the data bus is currently configured in.
+enum DataBus<Bus: ParallelDataBus> {
+    Output(Bus),
+    Input(Bus::Input),
+}
+
+/// Represents a parallel interface made of a data bus and WR, RD, DC and CS control pins. CS is
+/// active low, and DC is low for commands and high for data.
+pub struct ParallelInterface<Bus, Wr, Rd, Dc, Cs, Delay, Protocol>
+where
+    Bus: ParallelDataBus,
+    Wr: GpioPinOutput,
+    Rd: GpioPinOutput,
+    Dc: GpioPinOutput,
+    Cs: GpioPinOutput,
+    Delay: DelayNs,
+    Protocol: ParallelProtocol,
+{
+    /// The data bus. Only `None` while the bus is being turned around.
+    data: Option<DataBus<Bus>>,
+
+    /// The write strobe, or E for the 6800 protocol.
+    wr: Wr,
+
+    /// The read strobe, or R/W for the 6800 protocol.
+    rd: Rd,
+
+    /// The data/command select.
+    dc: Dc,
+
+    /// The chip select.
+    cs: Cs,
+
+    /// The delay source used to stretch the strobes.
+    delay: Delay,
+
+    /// The time the strobes are held active and inactive, in nanoseconds.
+    strobe_ns: u32,
+
+    /// The protocol.
+    _protocol: Protocol,
+}
+
+impl<Bus, Wr, Rd, Dc, Cs, Delay, Protocol> ParallelInterface<Bus, Wr, Rd, Dc, Cs, Delay, Protocol>
+where
+    Bus: ParallelDataBus,
+    Wr: GpioPinOutput,
+    Rd: GpioPinOutput,
+    Dc: GpioPinOutput,
+    Cs: GpioPinOutput,
+    Delay: DelayNs,
+    Protocol: ParallelProtocol,
+{
+    /// Creates a new parallel interface. The control lines are placed in their idle levels and
+    /// the device is deselected.
+    ///
+    /// # Arguments
+    /// `bus` - Provides the data bus.
+    /// `wr` - Provides the WR pin, or the E pin for the 6800 protocol.
+    /// `rd` - Provides the RD pin, or the R/W pin for the 6800 protocol.
+    /// `dc` - Provides the DC pin.
+    /// `cs` - Provides the CS pin.
+    /// `delay` - Provides the delay source used to stretch the strobes.
+    /// `protocol` - Provides the protocol.
+    ///
+    /// # Returns
+    /// A new parallel interface.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        bus: Bus,
+        mut wr: Wr,
+        mut rd: Rd,
+        mut dc: Dc,
+        mut cs: Cs,
+        delay: Delay,
+        protocol: Protocol,
+    ) -> Self {
+        cs.set();
+        dc.set();
+        Protocol::idle(&mut wr, &mut rd);
+
+        ParallelInterface {
+            data: Some(DataBus::Output(bus)),
+            wr,
+            rd,
+            dc,
+            cs,
+            delay,
+            strobe_ns: 0,
+            _protocol: protocol,
+        }
+    }
+
+    /// Sets the time the strobes are held active and inactive. Without a strobe time, the
+    /// strobes are as short as the pins can be toggled.
+    ///
+    /// # Arguments
+    /// `strobe_ns` - Provides the strobe time in nanoseconds.
+    pub fn set_strobe_time(&mut self, strobe_ns: u32) {
+        self.strobe_ns = strobe_ns;
+    }
+
+    /// Writes a command.
+    ///
+    /// # Arguments
+    /// `command` - Provides the command.
+    pub fn write_command<Word: ParallelWord>(&mut self, command: Word) {
+        self.cs.clear();
+        self.dc.clear();
+        self.write_cycle(command.into_bus_value());
+        self.cs.set();
+    }
+
+    /// Writes data words in a single burst.
+    ///
+    /// # Arguments
+    /// `words` - Provides the data words.
+    pub fn write_data<Word: ParallelWord>(&mut self, words: &[Word]) {
+        self.cs.clear();
+        self.dc.set();
+        for word in words {
+            self.write_cycle(word.into_bus_value());
+        }
+        self.cs.set();
+    }
+
+    /// Writes a command followed by its parameters, with the device selected throughout.
+    ///
+    /// # Arguments
+    /// `command` - Provides the command.
+    /// `words` - Provides the parameter words.
+    pub fn write_command_data<Word: ParallelWord>(&mut self, command: Word, words: &[Word]) {
+        self.cs.clear();
+        self.dc.clear();
+        self.write_cycle(command.into_bus_value());
+        self.dc.set();
+        for word in words {
+            self.write_cycle(word.into_bus_value());
+        }
+        self.cs.set();
+    }
+
+    /// Writes the same data word repeatedly, such as to fill a display area with a color.
+    ///
+    /// # Arguments
+    /// `word` - Provides the data word.
+    /// `count` - Provides the number of times the word is written.
+    pub fn write_data_repeated<Word: ParallelWord>(&mut self, word: Word, count: usize) {
+        self.cs.clear();
+        self.dc.set();
+        for _ in 0..count {
+            self.write_cycle(word.into_bus_value());
+        }
+        self.cs.set();
+    }
+
+    /// Reads data words in a single burst.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the data words.
+    pub fn read_data<Word: ParallelWord>(&mut self, buffer: &mut [Word]) {
+        self.cs.clear();
+        self.dc.set();
+        for word in buffer.iter_mut() {
+            *word = Word::from_bus_value(self.read_cycle());
+        }
+        self.cs.set();
+    }
+
+    /// Writes a command, then reads its response words, with the device selected throughout.
+    ///
+    /// # Arguments
+    /// `command` - Provides the command.
+    /// `buffer` - Receives the response words.
+    pub fn read_command_data<Word: ParallelWord>(&mut self, command: Word, buffer: &mut [Word]) {
+        self.cs.clear();
+        self.dc.clear();
+        self.write_cycle(command.into_bus_value());
+        self.dc.set();
+        for word in buffer.iter_mut() {
+            *word = Word::from_bus_value(self.read_cycle());
+        }
+        self.cs.set();
+    }
+
+    /// Extracts the data bus, the control pins and the delay source.
+    ///
+    /// # Returns
+    /// The data bus, the WR, RD, DC and CS pins, and the delay source.
+    pub fn extract_parts(mut self) -> (Bus, Wr, Rd, Dc, Cs, Delay) {
+        self.turn_around(true);
+
+        let bus = match self.data {
+            Some(DataBus::Output(bus)) => bus,
+            _ => unreachable!(),
+        };
+
+        (bus, self.wr, self.rd, self.dc, self.cs, self.delay)
+    }
+
+    /// Drives a word on the bus and strobes it into the device.
+    ///
+    /// # Arguments
+    /// `value` - Provides the bus value.
+    fn write_cycle(&mut self, value: usize) {
+        self.turn_around(true);
+
+        if let Some(DataBus::Output(bus)) = &mut self.data {
+            bus.write_value(value);
+        }
+
+        Protocol::begin_write(&mut self.wr, &mut self.rd);
+        self.wait_strobe();
+        Protocol::end_write(&mut self.wr, &mut self.rd);
+        self.wait_strobe();
+    }
+
+    /// Strobes a word out of the device and samples it from the bus.
+    ///
+    /// # Returns
+    /// The bus value.
+    fn read_cycle(&mut self) -> usize {
+        self.turn_around(false);
+
+        Protocol::begin_read(&mut self.wr, &mut self.rd);
+        self.wait_strobe();
+
+        let value = match &self.data {
+            Some(DataBus::Input(input)) => Bus::read_value(input),
+            _ => 0,
+        };
+
+        Protocol::end_read(&mut self.wr, &mut self.rd);
+        self.wait_strobe();
+        value
+    }
+
+    /// Configures the data bus direction. The bus is only driven once the device stopped driving
+    /// it, as read strobes always end before a turn around.
+    ///
+    /// # Arguments
+    /// `output` - Provides whether the bus should be driven.
+    fn turn_around(&mut self, output: bool) {
+        self.data = match self.data.take() {
+            Some(DataBus::Input(input)) if output => Some(DataBus::Output(Bus::from_input(input))),
+            Some(DataBus::Output(bus)) if !output => Some(DataBus::Input(bus.into_input())),
+            data => data,
+        };
+    }
+
+    /// Holds a strobe level for the configured strobe time.
+    fn wait_strobe(&mut self) {
+        if self.strobe_ns > 0 {
+            self.delay.delay_ns(self.strobe_ns);
+        }
+    }
+}
+
+//
+// For sealed traits.
+//
+
+mod private {
+    pub trait Sealed {}
+}
+
+impl private::Sealed for Intel8080 {}
+impl private::Sealed for Motorola6800 {}
+impl private::Sealed for u8 {}
+impl private::Sealed for u16 {}
+impl<const SIZE: usize, Section: PortSectionX<SIZE>> private::Sealed
+    for GpioSectionBus<SIZE, Section, GpioOut<PushPull>>
+{
+}
+impl<Port: PortX> private::Sealed for GpioPortBus<Port, GpioOut<PushPull>> {}
@@ -1,10 +1,6 @@
 //! # Pin
 //! The `pin` module includes structures and functions to abstract pins as software resources.
 
-//
-// TODO: Crate public "AlternatePin" type. <- Implements PinId + PinIdWithMode
-//
-
 //
 // TODO: Macro that implements "ToAlternate" functions for the correct pins.
 //
@@ -13,7 +9,11 @@
 // Dependencies.
 //
 
-use core::marker::PhantomData;
+use crate::gpio::{configure_unused_pins, set_pin_function, UnusedPinState};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicU16, Ordering},
+};
 use paste::paste;
 
 /// Describes a pin that can be identified by its port and pin offset.
@@ -76,7 +76,11 @@ impl<const PORT_NAME: char, const OFFSET: usize> PinId for Pin<PORT_NAME, OFFSET
     }
 }
 
-impl<const PORT_NAME: char, const OFFSET: usize> PinIdWithMode for Pin<PORT_NAME, OFFSET> {
+// - Private Note -
+// Every PinX is a main pin, which is always in its default GPIO mode. Implementing the mode for
+// every PinX lets drivers generic over PinX configure alternate functions.
+
+impl<Pin: PinX> PinIdWithMode for Pin {
     /// Gets the pin mode of the current pin.
     ///
     /// # Returns
@@ -88,13 +92,119 @@ impl<const PORT_NAME: char, const OFFSET: usize> PinIdWithMode for Pin<PORT_NAME
 
 impl<const PORT_NAME: char, const OFFSET: usize> PinX for Pin<PORT_NAME, OFFSET> {}
 
+/// Describes a pin that belongs to a port capable of generating interrupts.
+pub trait PinWithInterrupt: PinX {}
+
+// - Private Note -
+// Only ports 1 through 6 (ports A, B and C) have interrupt capabilities.
+
+impl<const OFFSET: usize> PinWithInterrupt for Pin<'A', OFFSET> {}
+impl<const OFFSET: usize> PinWithInterrupt for Pin<'B', OFFSET> {}
+impl<const OFFSET: usize> PinWithInterrupt for Pin<'C', OFFSET> {}
+
+/// Describes a pin that supports high drive strength outputs.
+pub trait PinWithHighDrive: PinX {}
+
+// - Private Note -
+// Only P2.0 to P2.3 (port A pins 8 to 11) support high drive strength.
+
+impl PinWithHighDrive for Pin<'A', 8> {}
+impl PinWithHighDrive for Pin<'A', 9> {}
+impl PinWithHighDrive for Pin<'A', 10> {}
+impl PinWithHighDrive for Pin<'A', 11> {}
+
+//
+// Reserved Pin structure.
+//
+
+/// Represents a pin on the MCU that is reserved for a dedicated function (crystal oscillators or
+/// debugging), and cannot be used as GPIO until released.
+pub struct ReservedPin<const PORT_NAME: char, const OFFSET: usize> {
+    pin: Pin<PORT_NAME, OFFSET>,
+}
+
+impl<const PORT_NAME: char, const OFFSET: usize> ReservedPin<PORT_NAME, OFFSET> {
+    /// Creates a new ReservedPin structure.
+    ///
+    /// # Returns
+    /// The instantiated ReservedPin.
+    const fn new() -> Self {
+        ReservedPin { pin: Pin::new() }
+    }
+
+    /// Releases the pin for GPIO use, regardless of the RazCAL configuration.
+    ///
+    /// # Returns
+    /// The released Pin.
+    ///
+    /// # Safety
+    /// Reconfiguring a crystal pin stops the oscillator, and reconfiguring a JTAG pin prevents
+    /// debugging the MCU.
+    pub unsafe fn release_unchecked(self) -> Pin<PORT_NAME, OFFSET> {
+        release_pins(PORT_NAME, 1 << OFFSET);
+        self.pin
+    }
+
+    /// Wraps a pin used by a driver of the dedicated function back into a reserved pin.
+    ///
+    /// # Arguments
+    /// `pin` - Provides the pin to reserve.
+    ///
+    /// # Returns
+    /// The ReservedPin.
+    pub(crate) fn from_pin(pin: Pin<PORT_NAME, OFFSET>) -> Self {
+        ReservedPin { pin }
+    }
+
+    /// Gets the underlying pin, for drivers of the dedicated function. The pin remains reserved.
+    ///
+    /// # Returns
+    /// The reserved pin.
+    pub(crate) fn into_pin(self) -> Pin<PORT_NAME, OFFSET> {
+        self.pin
+    }
+}
+
+/// Implements the safe release of reserved pins allowed by the RazCAL configuration.
+macro_rules! allow_pin_release {
+    ($config:meta, $port_char:literal, $($pin:literal),+) => {
+        $(
+            #[cfg($config)]
+            impl ReservedPin<$port_char, $pin> {
+                /// Releases the pin for GPIO use, as allowed by the RazCAL configuration.
+                ///
+                /// # Returns
+                /// The released Pin.
+                pub fn release(self) -> Pin<$port_char, $pin> {
+                    unsafe { self.release_unchecked() }
+                }
+            }
+        )+
+    };
+}
+
+allow_pin_release!(razcal_release_lfxt_pins, 'J', 0, 1);
+allow_pin_release!(razcal_release_hfxt_pins, 'J', 2, 3);
+allow_pin_release!(razcal_release_jtag_pins, 'J', 4, 5);
+
 macro_rules! define_pinset {
-    ($(($port:tt, $port_char:literal, $($pin:literal),+)),+) => {
+    (
+        $(($port:tt, $port_char:literal, $($pin:literal),+)),+;
+        reserved: ($rport:tt, $rport_char:literal, $($rpin:literal),+)
+    ) => {
         paste! {
 
             /// Singleton holding all the available pins on the MCU.
             static mut MCU_PINSET: Option<McuPinSet> = Some(McuPinSet::init_mcu_pins());
 
+            /// The pins available on the MCU, for each port.
+            const MCU_PIN_MASKS: &[(char, u16)] = &[
+                $(
+                    ($port_char, 0 $(| (1 << $pin))*),
+                )*
+                ($rport_char, 0 $(| (1 << $rpin))*),
+            ];
+
             /// Represents all the available pins on the current MCU.
             pub struct McuPinSet {
                 _marker: PhantomData<()>,
@@ -104,6 +214,10 @@ macro_rules! define_pinset {
                         pub [<p $port $pin>]: Pin<$port_char , $pin>,
                     )*
                 )*
+
+                $(
+                    pub [<p $rport $rpin>]: ReservedPin<$rport_char , $rpin>,
+                )*
             }
 
             impl McuPinSet {
@@ -121,6 +235,10 @@ macro_rules! define_pinset {
                                 [<p $port $pin>]: Pin::new(),
                             )*
                         )*
+
+                        $(
+                            [<p $rport $rpin>]: ReservedPin::new(),
+                        )*
                     }
                 }
 
@@ -135,6 +253,19 @@ macro_rules! define_pinset {
                         MCU_PINSET.take()
                     }
                 }
+
+                /// Places every pin that is not claimed by a GPIO instance, is not reserved, and is
+                /// not assigned to a peripheral, in a low-leakage state. Should be called once the
+                /// application has configured all the pins it uses.
+                ///
+                /// # Arguments
+                /// `state` - Provides the state to place the unused pins in.
+                pub fn finalize(state: UnusedPinState) {
+                    for (port_name, port_mask) in MCU_PIN_MASKS.iter() {
+                        let unused_mask = port_mask & !get_claimed_pins(*port_name);
+                        configure_unused_pins(*port_name, unused_mask, state);
+                    }
+                }
             }
         }
     };
@@ -145,8 +276,8 @@ define_pinset!(
     (a, 'A', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11),
     (b, 'B', 0, 1, 2, 3, 4, 5, 6, 7, 10, 11, 12, 13, 14, 15),
     (c, 'C', 0, 1, 2, 3, 4, 5, 6, 7, 14, 15),
-    (d, 'D', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9),
-    (j, 'J', 0, 1, 2, 3, 4, 5)
+    (d, 'D', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+    reserved: (j, 'J', 0, 1, 2, 3, 4, 5)
 );
 
 #[cfg(razcal_msp432_package = "nfbga")]
@@ -154,8 +285,8 @@ define_pinset!(
     (a, 'A', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15),
     (b, 'B', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15),
     (c, 'C', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15),
-    (d, 'D', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9),
-    (j, 'J', 0, 1, 2, 3, 4, 5)
+    (d, 'D', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+    reserved: (j, 'J', 0, 1, 2, 3, 4, 5)
 );
 
 #[cfg(razcal_msp432_package = "lqfp")]
@@ -164,15 +295,82 @@ define_pinset!(
     (b, 'B', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15),
     (c, 'C', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15),
     (d, 'D', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15),
-    (e, 'E', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15),
-    (j, 'J', 0, 1, 2, 3, 4, 5)
+    (e, 'E', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    reserved: (j, 'J', 0, 1, 2, 3, 4, 5)
 );
 
+//
+// Pin claim tracking.
+//
+
+/// Number of 16-bit ports (ports A, B, C, D, E and J).
+const PORT_COUNT: usize = 6;
+
+/// Reserved pins of port J, claimed until they are released.
+const PORT_J_RESERVED_PINS: u16 = 0x003F;
+
+/// Pins of each port that are claimed by a GPIO instance or reserved.
+static CLAIMED_PINS: [AtomicU16; PORT_COUNT] = [
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(0),
+    AtomicU16::new(PORT_J_RESERVED_PINS),
+];
+
+/// Marks pins as claimed, so that they are not considered unused.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to claim.
+pub(crate) fn claim_pins(port_name: char, mask: u16) {
+    CLAIMED_PINS[get_port_index(port_name)].fetch_or(mask, Ordering::Relaxed);
+}
+
+/// Marks pins as no longer claimed.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port the pins belong to.
+/// `mask` - Provides the pins to release.
+pub(crate) fn release_pins(port_name: char, mask: u16) {
+    CLAIMED_PINS[get_port_index(port_name)].fetch_and(!mask, Ordering::Relaxed);
+}
+
+/// Gets the index of a port within per-port tables.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port.
+///
+/// # Returns
+/// The index of the port.
+pub(crate) fn get_port_index(port_name: char) -> usize {
+    match port_name {
+        'J' => 5,
+        _ => {
+            debug_assert!(('A'..='E').contains(&port_name));
+            (port_name as usize) - ('A' as usize)
+        }
+    }
+}
+
+/// Gets the pins of a port that are claimed by a GPIO instance.
+///
+/// # Arguments
+/// `port_name` - Provides the name of the port.
+///
+/// # Returns
+/// The claimed pins.
+fn get_claimed_pins(port_name: char) -> u16 {
+    CLAIMED_PINS[get_port_index(port_name)].load(Ordering::Relaxed)
+}
+
 //
 // Alternate Pin Mode support.
 //
 
 /// Defines the possible modes for a pin.
+#[derive(Clone, Copy)]
 pub(crate) enum PinMode {
     DefaultGpio = 0,
     Alternate1 = 1,
@@ -189,6 +387,67 @@ pub(crate) trait PinIdWithMode: PinId + private::Sealed {
     fn get_mode(&self) -> PinMode;
 }
 
+/// Represents a pin on the MCU configured for one of its alternate functions.
+pub(crate) struct AlternatePin<Pin: PinIdWithMode> {
+    /// The actual pin.
+    pin: Pin,
+
+    /// The alternate function the pin is configured for.
+    mode: PinMode,
+}
+
+impl<Pin: PinIdWithMode> AlternatePin<Pin> {
+    /// Configures a pin for one of its alternate functions.
+    ///
+    /// # Arguments
+    /// `pin` - Provides the pin to configure.
+    /// `mode` - Provides the alternate function.
+    ///
+    /// # Returns
+    /// The AlternatePin.
+    pub(crate) fn new(pin: Pin, mode: PinMode) -> Self {
+        set_pin_function(&pin, mode);
+        AlternatePin { pin, mode }
+    }
+
+    /// Configures the pin back to its original function.
+    ///
+    /// # Returns
+    /// The original pin.
+    pub(crate) fn into_pin(self) -> Pin {
+        set_pin_function(&self, self.pin.get_mode());
+        self.pin
+    }
+}
+
+impl<Pin: PinIdWithMode> PinId for AlternatePin<Pin> {
+    /// Gets the name of the port this pin belongs to.
+    ///
+    /// # Returns
+    /// Port name.
+    fn get_port_name(&self) -> char {
+        self.pin.get_port_name()
+    }
+
+    /// Gets the offset of this pin within its owning port.
+    ///
+    /// # Returns
+    /// Offset.
+    fn get_offset(&self) -> u8 {
+        self.pin.get_offset()
+    }
+}
+
+impl<Pin: PinIdWithMode> PinIdWithMode for AlternatePin<Pin> {
+    /// Gets the pin mode of the current pin.
+    ///
+    /// # Returns
+    /// PinMode.
+    fn get_mode(&self) -> PinMode {
+        self.mode
+    }
+}
+
 //
 // For sealed traits.
 //
@@ -198,3 +457,4 @@ mod private {
 }
 
 impl<const PORT_NAME: char, const OFFSET: usize> private::Sealed for Pin<PORT_NAME, OFFSET> {}
+impl<Pin: PinIdWithMode> private::Sealed for AlternatePin<Pin> {}
@@ -33,6 +33,9 @@ pub trait PortId: private::Sealed {
 /// A trait that is a shorthabd for the `Port<...>` structure.
 pub trait PortX: private::Sealed + PortId {}
 
+/// Describes a port capable of generating interrupts.
+pub trait PortWithInterrupt: PortX {}
+
 //
 // Structures
 //
@@ -90,6 +93,13 @@ seq!(N in 0..16 {
     impl<const PORT_NAME: char> PortX for Port<PORT_NAME> {}
 });
 
+// - Private Note -
+// Only ports 1 through 6 (ports A, B and C) have interrupt capabilities.
+
+impl PortWithInterrupt for Port<'A'> {}
+impl PortWithInterrupt for Port<'B'> {}
+impl PortWithInterrupt for Port<'C'> {}
+
 //
 // For sealed traits.
 //
@@ -40,6 +40,12 @@ pub trait PortSectionX<const SIZE: usize>: private::Sealed {
     }
 }
 
+/// Describes a contiguous section of a port capable of generating interrupts.
+pub trait PortSectionWithInterrupt<const SIZE: usize>: PortSectionX<SIZE> {}
+
+/// Describes a contiguous section of a port that supports high drive strength outputs.
+pub trait PortSectionWithHighDrive<const SIZE: usize>: PortSectionX<SIZE> {}
+
 //
 // Structures.
 //
@@ -109,6 +115,24 @@ macro_rules! define_port_section {
                     }
                 }
 
+                // - Private Note -
+                // Only ports 1 through 6 (ports A, B and C) have interrupt capabilities.
+
+                impl<const OFFSET: usize> PortSectionWithInterrupt<$count> for [<PortSection $count>]<'A', OFFSET> where
+                    #([(); OFFSET + N]: ,)*
+                {
+                }
+
+                impl<const OFFSET: usize> PortSectionWithInterrupt<$count> for [<PortSection $count>]<'B', OFFSET> where
+                    #([(); OFFSET + N]: ,)*
+                {
+                }
+
+                impl<const OFFSET: usize> PortSectionWithInterrupt<$count> for [<PortSection $count>]<'C', OFFSET> where
+                    #([(); OFFSET + N]: ,)*
+                {
+                }
+
                 impl<const PORT_NAME: char, const OFFSET: usize> private::Sealed for [<PortSection $count>]<PORT_NAME, OFFSET> where
                     #([(); OFFSET + N]: ,)*
                 {
@@ -122,6 +146,16 @@ seq!(N in 2..16 {
     define_port_section!(N);
 });
 
+// - Private Note -
+// Only P2.0 to P2.3 (port A pins 8 to 11) support high drive strength.
+
+impl PortSectionWithHighDrive<2> for PortSection2<'A', 8> {}
+impl PortSectionWithHighDrive<2> for PortSection2<'A', 9> {}
+impl PortSectionWithHighDrive<2> for PortSection2<'A', 10> {}
+impl PortSectionWithHighDrive<3> for PortSection3<'A', 8> {}
+impl PortSectionWithHighDrive<3> for PortSection3<'A', 9> {}
+impl PortSectionWithHighDrive<4> for PortSection4<'A', 8> {}
+
 //
 // For sealed traits.
 //
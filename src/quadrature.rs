@@ -0,0 +1,262 @@
+//! # Quadrature
+//! The `quadrature` module includes structures and functions to decode the quadrature signals of
+//! rotary encoders over two GPIO input pins.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{GpioIn, GpioInputMode, GpioPin, GpioPinInput},
+    pin::PinWithInterrupt,
+    Edge,
+};
+
+//
+// Consts
+//
+
+/// Marks a transition where both signals changed at once, so the direction is unknown.
+const INVALID: i8 = i8::MIN;
+
+/// Position change for every transition between two states, indexed by the previous state times
+/// four plus the new state. A state holds signal A in bit 1 and signal B in bit 0, and forward
+/// rotation follows the 00, 10, 11, 01 sequence.
+const TRANSITION_TABLE: [i8; 16] = [
+    0, -1, 1, INVALID, // From 00.
+    1, 0, INVALID, -1, // From 01.
+    -1, INVALID, 0, 1, // From 10.
+    INVALID, 1, -1, 0, // From 11.
+];
+
+//
+// Structures
+//
+
+/// Represents the number of counts per quadrature cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuadratureResolution {
+    /// One count per cycle.
+    X1 = 4,
+
+    /// Two counts per cycle.
+    X2 = 2,
+
+    /// Four counts per cycle, one for every edge of either signal.
+    X4 = 1,
+}
+
+/// Represents a quadrature decoder over two GPIO input pins.
+pub struct QuadratureDecoder<A: GpioPinInput, B: GpioPinInput> {
+    /// The A signal input.
+    a: A,
+
+    /// The B signal input.
+    b: B,
+
+    /// The last decoded state.
+    state: u8,
+
+    /// The position in edges of either signal.
+    edges: i32,
+
+    /// The number of edges per count.
+    resolution: QuadratureResolution,
+
+    /// The number of transitions rejected since the last check.
+    invalid_transitions: u32,
+
+    /// The position at the last velocity estimation, in counts.
+    velocity_position: i32,
+
+    /// Whether the pin interrupts are re-armed after every update.
+    interrupts_enabled: bool,
+}
+
+impl<A: GpioPinInput, B: GpioPinInput> QuadratureDecoder<A, B> {
+    /// Creates a new quadrature decoder at position 0.
+    ///
+    /// # Arguments
+    /// `a` - Provides the A signal input pin.
+    /// `b` - Provides the B signal input pin.
+    /// `resolution` - Provides the number of counts per quadrature cycle.
+    ///
+    /// # Returns
+    /// A new quadrature decoder.
+    pub fn new(a: A, b: B, resolution: QuadratureResolution) -> Self {
+        let state = read_state(&a, &b);
+
+        QuadratureDecoder {
+            a,
+            b,
+            state,
+            edges: 0,
+            resolution,
+            invalid_transitions: 0,
+            velocity_position: 0,
+            interrupts_enabled: false,
+        }
+    }
+
+    /// Samples the signals and updates the position. In polling mode, should be called more often
+    /// than the signals can change.
+    pub fn update(&mut self) {
+        let state = read_state(&self.a, &self.b);
+        self.decode(state);
+    }
+
+    /// Gets the position.
+    ///
+    /// # Returns
+    /// The signed position, in counts of the configured resolution.
+    pub fn get_position(&self) -> i32 {
+        self.edges.div_euclid(self.resolution as i32)
+    }
+
+    /// Sets the position, such as to zero it at a reference point.
+    ///
+    /// # Arguments
+    /// `position` - Provides the position, in counts of the configured resolution.
+    pub fn set_position(&mut self, position: i32) {
+        self.edges = position * (self.resolution as i32);
+        self.velocity_position = position;
+    }
+
+    /// Gets and resets the number of transitions where both signals changed at once. Such
+    /// transitions are ignored, and indicate the decoder is updated too slowly.
+    ///
+    /// # Returns
+    /// The number of rejected transitions since the last call.
+    pub fn take_invalid_transitions(&mut self) -> u32 {
+        let invalid_transitions = self.invalid_transitions;
+        self.invalid_transitions = 0;
+        invalid_transitions
+    }
+
+    /// Estimates the velocity from the position change since the last estimation.
+    ///
+    /// # Arguments
+    /// `elapsed_us` - Provides the time since the last estimation, in microseconds.
+    ///
+    /// # Returns
+    /// The velocity, in counts per second.
+    pub fn get_velocity(&mut self, elapsed_us: u32) -> i32 {
+        let position = self.get_position();
+        let delta = (position - self.velocity_position) as i64;
+        self.velocity_position = position;
+
+        if elapsed_us == 0 {
+            return 0;
+        }
+
+        (delta * 1_000_000 / elapsed_us as i64) as i32
+    }
+
+    /// Extracts the input pins.
+    ///
+    /// # Returns
+    /// The A and B signal input pins.
+    pub fn extract_pins(self) -> (A, B) {
+        (self.a, self.b)
+    }
+
+    /// Updates the position from a new state.
+    ///
+    /// # Arguments
+    /// `state` - Provides the new state.
+    fn decode(&mut self, state: u8) {
+        match TRANSITION_TABLE[((self.state << 2) | state) as usize] {
+            INVALID => self.invalid_transitions += 1,
+            change => self.edges += change as i32,
+        }
+
+        self.state = state;
+    }
+}
+
+/// The following implements interrupt mode for decoders with interrupt capable pins.
+impl<PinA, ModeA, PinB, ModeB>
+    QuadratureDecoder<GpioPin<PinA, GpioIn<ModeA>>, GpioPin<PinB, GpioIn<ModeB>>>
+where
+    PinA: PinWithInterrupt,
+    ModeA: GpioInputMode,
+    PinB: PinWithInterrupt,
+    ModeB: GpioInputMode,
+{
+    /// Enables interrupt mode. Both pins raise an interrupt on their next edge, and the handler
+    /// should call `update_from_interrupt`.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function invoked when either signal changes.
+    pub fn enable_interrupts(&mut self, handler: fn()) {
+        self.a.set_interrupt_handler(handler);
+        self.b.set_interrupt_handler(handler);
+        self.interrupts_enabled = true;
+        self.update_from_interrupt();
+    }
+
+    /// Disables interrupt mode. The position is then only updated by polling.
+    pub fn disable_interrupts(&mut self) {
+        self.a.disable_interrupt();
+        self.b.disable_interrupt();
+        self.a.clear_interrupt_handler();
+        self.b.clear_interrupt_handler();
+        self.interrupts_enabled = false;
+    }
+
+    /// Samples the signals, updates the position and re-arms both pin interrupts for the edge
+    /// opposite to their current level.
+    pub fn update_from_interrupt(&mut self) {
+        if !self.interrupts_enabled {
+            self.update();
+            return;
+        }
+
+        // A signal may change while the interrupts are re-armed, so sample again until the
+        // armed edges match the signals.
+
+        loop {
+            let state = read_state(&self.a, &self.b);
+            self.decode(state);
+
+            self.a.enable_interrupt(get_next_edge(self.a.read()));
+            self.b.enable_interrupt(get_next_edge(self.b.read()));
+
+            if read_state(&self.a, &self.b) == state {
+                break;
+            }
+        }
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Reads the state of the signals.
+///
+/// # Arguments
+/// `a` - Provides the A signal input.
+/// `b` - Provides the B signal input.
+///
+/// # Returns
+/// The state, with signal A in bit 1 and signal B in bit 0.
+fn read_state<A: GpioPinInput, B: GpioPinInput>(a: &A, b: &B) -> u8 {
+    ((a.read() as u8) << 1) | (b.read() as u8)
+}
+
+/// Gets the edge a signal produces when it next changes.
+///
+/// # Arguments
+/// `level` - Provides the current level of the signal.
+///
+/// # Returns
+/// `FallingEdge` if the signal is high.
+/// `RisingEdge` otherwise.
+fn get_next_edge(level: bool) -> Edge {
+    if level {
+        Edge::FallingEdge
+    } else {
+        Edge::RisingEdge
+    }
+}
@@ -0,0 +1,220 @@
+//! # Ring Buffer
+//! The `ringbuffer` module includes a single producer, single consumer byte queue shared between
+//! an interrupt service routine and the main program, over caller supplied storage.
+
+//
+// Dependencies
+//
+
+use core::{
+    ptr, slice,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+//
+// Structures
+//
+
+/// Represents a lock-free queue of bytes with a single producer and a single consumer. The
+/// producer only advances the head, and the consumer only advances the tail, so either may run
+/// in an interrupt service routine.
+pub(crate) struct RingBuffer {
+    /// The storage holding the bytes.
+    storage: AtomicPtr<u8>,
+
+    /// The number of bytes the storage can hold.
+    capacity: AtomicUsize,
+
+    /// The index of the next byte pushed, counted modulo twice the capacity, so that a full
+    /// ring buffer can be told from an empty one whatever the capacity.
+    head: AtomicUsize,
+
+    /// The index of the next byte popped, counted modulo twice the capacity.
+    tail: AtomicUsize,
+}
+
+impl RingBuffer {
+    /// Creates a new ring buffer without storage, which can hold no bytes.
+    ///
+    /// # Returns
+    /// A new ring buffer.
+    pub(crate) const fn new() -> Self {
+        RingBuffer {
+            storage: AtomicPtr::new(ptr::null_mut()),
+            capacity: AtomicUsize::new(0),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Attaches the storage of the ring buffer and empties it. Neither the producer nor the
+    /// consumer may use the ring buffer during the call.
+    ///
+    /// # Arguments
+    /// `storage` - Provides the storage.
+    pub(crate) fn attach(&self, storage: &'static mut [u8]) {
+        self.capacity.store(0, Ordering::Release);
+        self.head.store(0, Ordering::Relaxed);
+        self.tail.store(0, Ordering::Relaxed);
+        self.storage.store(storage.as_mut_ptr(), Ordering::Relaxed);
+        self.capacity.store(storage.len(), Ordering::Release);
+    }
+
+    /// Detaches the storage of the ring buffer, discarding its content. Neither the producer nor
+    /// the consumer may use the ring buffer during the call.
+    ///
+    /// # Returns
+    /// The storage provided to `attach`.
+    pub(crate) fn detach(&self) -> &'static mut [u8] {
+        let capacity = self.capacity.swap(0, Ordering::Acquire);
+        let storage = self.storage.swap(ptr::null_mut(), Ordering::Relaxed);
+
+        if storage.is_null() {
+            return &mut [];
+        }
+
+        // The storage was provided as a unique static slice, which is returned as such.
+
+        unsafe { slice::from_raw_parts_mut(storage, capacity) }
+    }
+
+    /// Adds a byte at the head of the ring buffer. Should only be called by the producer.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the byte to add.
+    ///
+    /// # Returns
+    /// `true` if the byte was added.
+    /// `false` if the ring buffer is full.
+    pub(crate) fn push(&self, byte: u8) -> bool {
+        let capacity = self.capacity.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if get_count(head, tail, capacity) >= capacity {
+            return false;
+        }
+
+        unsafe {
+            ptr::write_volatile(
+                self.storage
+                    .load(Ordering::Relaxed)
+                    .add(get_slot(head, capacity)),
+                byte,
+            )
+        };
+
+        self.head
+            .store(get_next_index(head, capacity), Ordering::Release);
+        true
+    }
+
+    /// Removes the byte at the tail of the ring buffer. Should only be called by the consumer.
+    ///
+    /// # Returns
+    /// `Some(byte)` if the ring buffer was not empty.
+    /// `None` otherwise.
+    pub(crate) fn pop(&self) -> Option<u8> {
+        let capacity = self.capacity.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+
+        if head == tail || capacity == 0 {
+            return None;
+        }
+
+        let byte = unsafe {
+            ptr::read_volatile(
+                self.storage
+                    .load(Ordering::Relaxed)
+                    .add(get_slot(tail, capacity)),
+            )
+        };
+
+        self.tail
+            .store(get_next_index(tail, capacity), Ordering::Release);
+        Some(byte)
+    }
+
+    /// Gets the number of bytes in the ring buffer.
+    ///
+    /// # Returns
+    /// The number of bytes that can be popped.
+    pub(crate) fn len(&self) -> usize {
+        let capacity = self.capacity.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        get_count(self.head.load(Ordering::Acquire), tail, capacity)
+    }
+
+    /// Checks if the ring buffer is empty.
+    ///
+    /// # Returns
+    /// `true` if no byte can be popped.
+    /// `false` otherwise.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the number of bytes that can still be pushed.
+    ///
+    /// # Returns
+    /// The free space of the ring buffer.
+    pub(crate) fn get_free_space(&self) -> usize {
+        self.capacity
+            .load(Ordering::Acquire)
+            .saturating_sub(self.len())
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Gets the number of bytes between two indices.
+///
+/// # Arguments
+/// `head` - Provides the index of the next byte pushed.
+/// `tail` - Provides the index of the next byte popped.
+/// `capacity` - Provides the number of bytes the storage can hold.
+///
+/// # Returns
+/// The number of bytes in the ring buffer.
+fn get_count(head: usize, tail: usize, capacity: usize) -> usize {
+    if head >= tail {
+        head - tail
+    } else {
+        head + 2 * capacity - tail
+    }
+}
+
+/// Gets the index following another one, wrapping around at twice the capacity.
+///
+/// # Arguments
+/// `index` - Provides the index.
+/// `capacity` - Provides the number of bytes the storage can hold.
+///
+/// # Returns
+/// The next index.
+fn get_next_index(index: usize, capacity: usize) -> usize {
+    if index + 1 >= 2 * capacity {
+        0
+    } else {
+        index + 1
+    }
+}
+
+/// Gets the storage slot of an index.
+///
+/// # Arguments
+/// `index` - Provides the index.
+/// `capacity` - Provides the number of bytes the storage can hold.
+///
+/// # Returns
+/// The offset of the byte in the storage.
+fn get_slot(index: usize, capacity: usize) -> usize {
+    if index >= capacity {
+        index - capacity
+    } else {
+        index
+    }
+}
@@ -0,0 +1,230 @@
+//! # Device
+//! The `device` module includes structures and functions to share a SPI master between devices,
+//! each with its own chip select pin and SPI mode.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::GpioPinOutput,
+    spi::{
+        get_clock_mode_bits, set_clock_mode, ClockPhase, ClockPolarity, LowIdle, RisingEdgeSample,
+        SpiInstance, SpiMaster, SpiMode,
+    },
+};
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use embedded_hal::{
+    delay::DelayNs,
+    spi::{self, ErrorKind, ErrorType, Operation},
+};
+
+//
+// Structures
+//
+
+/// Represents the errors of a SPI device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpiDeviceError {
+    /// Another device is in the middle of a transaction, such as one interrupted by the current
+    /// one.
+    BusBusy,
+}
+
+impl spi::Error for SpiDeviceError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// Represents a SPI master shared between devices. Every device locks the master for the duration
+/// of its transactions, and sets the SPI mode it requires.
+pub struct SharedSpiBus<Instance: SpiInstance> {
+    /// The SPI master. Its mode typestate is not relevant, as every device sets its own mode.
+    master: UnsafeCell<SpiMaster<Instance, LowIdle, RisingEdgeSample>>,
+
+    /// Whether a device is in the middle of a transaction.
+    locked: AtomicBool,
+}
+
+// - Private Note -
+// The master is only accessed by the device holding the lock, so the bus can be shared between
+// the main program and interrupt service routines.
+
+unsafe impl<Instance: SpiInstance> Sync for SharedSpiBus<Instance> {}
+
+impl<Instance: SpiInstance> SharedSpiBus<Instance> {
+    /// Creates a new shared SPI bus.
+    ///
+    /// # Arguments
+    /// `master` - Provides the SPI master to share.
+    ///
+    /// # Returns
+    /// A new shared SPI bus.
+    pub fn new<Polarity: ClockPolarity, Phase: ClockPhase>(
+        master: SpiMaster<Instance, Polarity, Phase>,
+    ) -> Self {
+        SharedSpiBus {
+            master: UnsafeCell::new(master.into_mode(SpiMode::new())),
+            locked: AtomicBool::new(false),
+        }
+    }
+
+    /// Extracts the SPI master once no device uses the bus anymore.
+    ///
+    /// # Arguments
+    /// `mode` - Provides the clock polarity and phase the master is left in.
+    ///
+    /// # Returns
+    /// The SPI master.
+    pub fn into_inner<Polarity: ClockPolarity, Phase: ClockPhase>(
+        self,
+        mode: SpiMode<Polarity, Phase>,
+    ) -> SpiMaster<Instance, Polarity, Phase> {
+        self.master.into_inner().into_mode(mode)
+    }
+
+    /// Locks the SPI master for a transaction.
+    ///
+    /// # Returns
+    /// `Some(master)` if the bus was not locked.
+    /// `None` otherwise.
+    #[allow(clippy::mut_from_ref)]
+    fn lock(&self) -> Option<&mut SpiMaster<Instance, LowIdle, RisingEdgeSample>> {
+        if self.locked.swap(true, Ordering::Acquire) {
+            return None;
+        }
+
+        Some(unsafe { &mut *self.master.get() })
+    }
+
+    /// Unlocks the SPI master at the end of a transaction.
+    fn unlock(&self) {
+        self.locked.store(false, Ordering::Release);
+    }
+}
+
+/// Represents a device on a shared SPI bus, selected by driving its chip select pin low.
+pub struct SpiDevice<'a, Instance, Cs, Delay, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Cs: GpioPinOutput,
+    Delay: DelayNs,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    /// The shared SPI bus.
+    bus: &'a SharedSpiBus<Instance>,
+
+    /// The chip select output.
+    cs: Cs,
+
+    /// The delay source used for the delay operations.
+    delay: Delay,
+
+    /// The SPI mode of the device.
+    _mode: SpiMode<Polarity, Phase>,
+}
+
+impl<'a, Instance, Cs, Delay, Polarity, Phase> SpiDevice<'a, Instance, Cs, Delay, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Cs: GpioPinOutput,
+    Delay: DelayNs,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    /// Creates a new SPI device. The device is deselected.
+    ///
+    /// # Arguments
+    /// `bus` - Provides the shared SPI bus the device is on.
+    /// `cs` - Provides the chip select output pin.
+    /// `delay` - Provides the delay source used for the delay operations.
+    /// `mode` - Provides the clock polarity and phase of the device.
+    ///
+    /// # Returns
+    /// A new SPI device.
+    pub fn new(
+        bus: &'a SharedSpiBus<Instance>,
+        mut cs: Cs,
+        delay: Delay,
+        mode: SpiMode<Polarity, Phase>,
+    ) -> Self {
+        cs.set();
+
+        SpiDevice {
+            bus,
+            cs,
+            delay,
+            _mode: mode,
+        }
+    }
+
+    /// Extracts the chip select pin and delay source.
+    ///
+    /// # Returns
+    /// The chip select output pin and the delay source.
+    pub fn extract_parts(self) -> (Cs, Delay) {
+        (self.cs, self.delay)
+    }
+}
+
+//
+// embedded-hal traits.
+//
+
+impl<Instance, Cs, Delay, Polarity, Phase> ErrorType
+    for SpiDevice<'_, Instance, Cs, Delay, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Cs: GpioPinOutput,
+    Delay: DelayNs,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    type Error = SpiDeviceError;
+}
+
+impl<Instance, Cs, Delay, Polarity, Phase> spi::SpiDevice
+    for SpiDevice<'_, Instance, Cs, Delay, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Cs: GpioPinOutput,
+    Delay: DelayNs,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let master = self.bus.lock().ok_or(SpiDeviceError::BusBusy)?;
+
+        // The mode must be set before selecting the device, so the clock is already idling at
+        // the right level.
+
+        set_clock_mode::<Instance>(get_clock_mode_bits::<Polarity, Phase>());
+        self.cs.clear();
+
+        for operation in operations {
+            match operation {
+                Operation::Read(words) => master.read(words),
+                Operation::Write(words) => master.write(words),
+                Operation::Transfer(read, write) => {
+                    let _ = spi::SpiBus::transfer(master, read, write);
+                }
+                Operation::TransferInPlace(words) => master.transfer(words),
+                Operation::DelayNs(ns) => {
+                    master.flush();
+                    self.delay.delay_ns(*ns);
+                }
+            }
+        }
+
+        master.flush();
+        self.cs.set();
+        self.bus.unlock();
+
+        Ok(())
+    }
+}
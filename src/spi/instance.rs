@@ -0,0 +1,67 @@
+//! # Instance
+//! The `instance` module maps the eUSCI modules to the pins carrying their SPI signals.
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{
+        EusciA0, EusciA1, EusciA2, EusciA3, EusciB0, EusciB1, EusciB2, EusciB3, EusciInstance,
+    },
+    pin::{Pin, PinX},
+};
+
+//
+// Traits
+//
+
+/// An eUSCI module that can operate in SPI mode.
+pub trait SpiInstance: EusciInstance {
+    /// The pin carrying the serial clock.
+    type ClockPin: PinX;
+
+    /// The pin carrying the slave in, master out data.
+    type SimoPin: PinX;
+
+    /// The pin carrying the slave out, master in data.
+    type SomiPin: PinX;
+
+    /// The pin carrying the slave transmit enable.
+    type StePin: PinX;
+}
+
+//
+// Structures
+//
+
+/// The serial clock, slave in master out and slave out master in pins of an eUSCI module.
+pub type SpiPins<Instance> = (
+    <Instance as SpiInstance>::ClockPin,
+    <Instance as SpiInstance>::SimoPin,
+    <Instance as SpiInstance>::SomiPin,
+);
+
+macro_rules! impl_spi_instance {
+    ($($instance:ident, $clock:ty, $simo:ty, $somi:ty, $ste:ty;)+) => {
+        $(
+            impl SpiInstance for $instance {
+                type ClockPin = $clock;
+                type SimoPin = $simo;
+                type SomiPin = $somi;
+                type StePin = $ste;
+            }
+        )+
+    };
+}
+
+impl_spi_instance!(
+    EusciA0, Pin<'A', 1>, Pin<'A', 3>, Pin<'A', 2>, Pin<'A', 0>;
+    EusciA1, Pin<'A', 9>, Pin<'A', 11>, Pin<'A', 10>, Pin<'A', 8>;
+    EusciA2, Pin<'B', 1>, Pin<'B', 3>, Pin<'B', 2>, Pin<'B', 0>;
+    EusciA3, Pin<'E', 5>, Pin<'E', 7>, Pin<'E', 6>, Pin<'E', 4>;
+    EusciB0, Pin<'A', 5>, Pin<'A', 6>, Pin<'A', 7>, Pin<'A', 4>;
+    EusciB1, Pin<'C', 11>, Pin<'C', 12>, Pin<'C', 13>, Pin<'C', 10>;
+    EusciB2, Pin<'B', 5>, Pin<'B', 6>, Pin<'B', 7>, Pin<'B', 4>;
+    EusciB3, Pin<'E', 9>, Pin<'E', 10>, Pin<'E', 11>, Pin<'E', 8>;
+);
@@ -0,0 +1,370 @@
+//! # Master
+//! The `master` module includes structures and functions to utilize an eUSCI module as a SPI
+//! master.
+
+//
+// Dependencies
+//
+
+use crate::{
+    clock::ClockFrequencies,
+    eusci::{
+        configure_eusci, get_eusci_registers, select_prescaled_clock, UCBUSY_MASK, UCRXIFG_MASK,
+        UCSSEL_MASK, UCSWRST_MASK, UCSYNC_MASK, UCTXIFG_MASK,
+    },
+    pin::{AlternatePin, PinMode},
+    spi::{BitOrder, ClockPhase, ClockPolarity, SpiInstance, SpiMode},
+};
+use core::convert::Infallible;
+use embedded_hal::spi::{ErrorType, SpiBus};
+
+//
+// Consts
+//
+
+pub(crate) const UCCKPH_MASK: u16 = 1 << 15;
+pub(crate) const UCCKPL_MASK: u16 = 1 << 14;
+pub(crate) const UCMSB_MASK: u16 = 1 << 13;
+pub(crate) const UC7BIT_MASK: u16 = 1 << 12;
+pub(crate) const UCMST_MASK: u16 = 1 << 11;
+pub(crate) const UCMODE_SHIFT: u16 = 9;
+pub(crate) const UCMODE_MASK: u16 = 0x3 << UCMODE_SHIFT;
+
+//
+// Structures
+//
+
+/// Represents the number of bits in a SPI character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharacterLength {
+    SevenBit,
+    EightBit,
+}
+
+/// Represents an eUSCI module operating as a SPI master, driving the clock on its SCLK pin.
+pub struct SpiMaster<Instance, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    /// The eUSCI module.
+    instance: Instance,
+
+    /// The serial clock pin.
+    clock: AlternatePin<Instance::ClockPin>,
+
+    /// The slave in, master out pin.
+    simo: AlternatePin<Instance::SimoPin>,
+
+    /// The slave out, master in pin.
+    somi: AlternatePin<Instance::SomiPin>,
+
+    /// The actual bit rate, in Hz.
+    bit_rate_hz: u32,
+
+    /// The SPI mode.
+    _mode: SpiMode<Polarity, Phase>,
+}
+
+impl<Instance, Polarity, Phase> SpiMaster<Instance, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    /// Creates a new SPI master, shifting 8-bit characters MSB first.
+    ///
+    /// # Arguments
+    /// `instance` - Provides the eUSCI module.
+    /// `clock` - Provides the serial clock pin of the module.
+    /// `simo` - Provides the slave in, master out pin of the module.
+    /// `somi` - Provides the slave out, master in pin of the module.
+    /// `mode` - Provides the clock polarity and phase.
+    /// `bit_rate_hz` - Provides the desired bit rate. The actual bit rate is the closest one the
+    ///   clocks can produce, and can be checked with `get_bit_rate`.
+    /// `clocks` - Provides the clock frequencies.
+    ///
+    /// # Returns
+    /// A new SPI master.
+    pub fn new(
+        instance: Instance,
+        clock: Instance::ClockPin,
+        simo: Instance::SimoPin,
+        somi: Instance::SomiPin,
+        mode: SpiMode<Polarity, Phase>,
+        bit_rate_hz: u32,
+        clocks: &ClockFrequencies,
+    ) -> Self {
+        let (source, prescaler, actual_hz) = select_prescaled_clock(clocks, bit_rate_hz);
+
+        configure_eusci::<Instance, _>(|regs| {
+            let control = UCMSB_MASK
+                | UCMST_MASK
+                | UCSYNC_MASK
+                | source.get_select_bits()
+                | get_clock_mode_bits::<Polarity, Phase>();
+
+            // The module is held in reset while the configuration is written.
+
+            regs.control_0.write(control | UCSWRST_MASK);
+            regs.baud_rate.write(prescaler);
+            regs.interrupt_enable.write(0);
+        });
+
+        SpiMaster {
+            instance,
+            clock: AlternatePin::new(clock, PinMode::Alternate1),
+            simo: AlternatePin::new(simo, PinMode::Alternate1),
+            somi: AlternatePin::new(somi, PinMode::Alternate1),
+            bit_rate_hz: actual_hz,
+            _mode: mode,
+        }
+    }
+
+    /// Changes the clock polarity and phase.
+    ///
+    /// # Arguments
+    /// `mode` - Provides the new clock polarity and phase.
+    ///
+    /// # Returns
+    /// The SPI master in the new mode.
+    pub fn into_mode<NewPolarity: ClockPolarity, NewPhase: ClockPhase>(
+        mut self,
+        mode: SpiMode<NewPolarity, NewPhase>,
+    ) -> SpiMaster<Instance, NewPolarity, NewPhase> {
+        self.flush();
+        set_clock_mode::<Instance>(get_clock_mode_bits::<NewPolarity, NewPhase>());
+
+        SpiMaster {
+            instance: self.instance,
+            clock: self.clock,
+            simo: self.simo,
+            somi: self.somi,
+            bit_rate_hz: self.bit_rate_hz,
+            _mode: mode,
+        }
+    }
+
+    /// Sets the order in which the bits of a character are shifted.
+    ///
+    /// # Arguments
+    /// `bit_order` - Provides the bit order.
+    pub fn set_bit_order(&mut self, bit_order: BitOrder) {
+        configure_eusci::<Instance, _>(|regs| match bit_order {
+            BitOrder::MsbFirst => regs.control_0.set_bits(UCMSB_MASK),
+            BitOrder::LsbFirst => regs.control_0.clear_bits(UCMSB_MASK),
+        });
+    }
+
+    /// Sets the number of bits in a character.
+    ///
+    /// # Arguments
+    /// `length` - Provides the character length.
+    pub fn set_character_length(&mut self, length: CharacterLength) {
+        configure_eusci::<Instance, _>(|regs| match length {
+            CharacterLength::SevenBit => regs.control_0.set_bits(UC7BIT_MASK),
+            CharacterLength::EightBit => regs.control_0.clear_bits(UC7BIT_MASK),
+        });
+    }
+
+    /// Sets the bit rate, selecting the clock source and prescaler again.
+    ///
+    /// # Arguments
+    /// `bit_rate_hz` - Provides the desired bit rate.
+    /// `clocks` - Provides the clock frequencies.
+    pub fn set_bit_rate(&mut self, bit_rate_hz: u32, clocks: &ClockFrequencies) {
+        let (source, prescaler, actual_hz) = select_prescaled_clock(clocks, bit_rate_hz);
+
+        configure_eusci::<Instance, _>(|regs| {
+            regs.control_0
+                .modify(|value| (value & !UCSSEL_MASK) | source.get_select_bits());
+            regs.baud_rate.write(prescaler);
+        });
+
+        self.bit_rate_hz = actual_hz;
+    }
+
+    /// Gets the actual bit rate.
+    ///
+    /// # Returns
+    /// Bit rate in Hz.
+    pub fn get_bit_rate(&self) -> u32 {
+        self.bit_rate_hz
+    }
+
+    /// Simultaneously sends and receives a character.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the character to send.
+    ///
+    /// # Returns
+    /// The received character.
+    pub fn transfer_byte(&mut self, byte: u8) -> u8 {
+        let regs = get_eusci_registers::<Instance>();
+
+        while (regs.interrupt_flag.read() & UCTXIFG_MASK) == 0 {}
+        regs.transmit_buffer.write(byte as u16);
+
+        while (regs.interrupt_flag.read() & UCRXIFG_MASK) == 0 {}
+        regs.receive_buffer.read() as u8
+    }
+
+    /// Simultaneously sends and receives characters, replacing each sent character with the one
+    /// received.
+    ///
+    /// # Arguments
+    /// `words` - Provides the characters to send, and receives the characters read.
+    pub fn transfer(&mut self, words: &mut [u8]) {
+        for word in words.iter_mut() {
+            *word = self.transfer_byte(*word);
+        }
+    }
+
+    /// Sends characters, discarding the characters received.
+    ///
+    /// # Arguments
+    /// `words` - Provides the characters to send.
+    pub fn write(&mut self, words: &[u8]) {
+        for word in words {
+            self.transfer_byte(*word);
+        }
+    }
+
+    /// Receives characters, sending zeros.
+    ///
+    /// # Arguments
+    /// `words` - Receives the characters read.
+    pub fn read(&mut self, words: &mut [u8]) {
+        for word in words.iter_mut() {
+            *word = self.transfer_byte(0);
+        }
+    }
+
+    /// Waits until the module is done shifting.
+    pub fn flush(&mut self) {
+        let regs = get_eusci_registers::<Instance>();
+
+        while (regs.status.read() & UCBUSY_MASK) != 0 {}
+    }
+
+    /// Holds the module in reset and releases its pins.
+    ///
+    /// # Returns
+    /// The eUSCI module, and the serial clock, slave in master out and slave out master in pins.
+    pub fn extract_parts(
+        mut self,
+    ) -> (
+        Instance,
+        Instance::ClockPin,
+        Instance::SimoPin,
+        Instance::SomiPin,
+    ) {
+        self.flush();
+        get_eusci_registers::<Instance>()
+            .control_0
+            .set_bits(UCSWRST_MASK);
+
+        (
+            self.instance,
+            self.clock.into_pin(),
+            self.simo.into_pin(),
+            self.somi.into_pin(),
+        )
+    }
+}
+
+//
+// embedded-hal traits.
+//
+
+impl<Instance, Polarity, Phase> ErrorType for SpiMaster<Instance, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    type Error = Infallible;
+}
+
+impl<Instance, Polarity, Phase> SpiBus for SpiMaster<Instance, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        SpiMaster::read(self, words);
+        Ok(())
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        SpiMaster::write(self, words);
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        // Zeros are sent past the end of the write buffer, and the characters received past the
+        // end of the read buffer are discarded.
+
+        for index in 0..read.len().max(write.len()) {
+            let received = self.transfer_byte(write.get(index).copied().unwrap_or(0));
+
+            if let Some(word) = read.get_mut(index) {
+                *word = received;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        SpiMaster::transfer(self, words);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        SpiMaster::flush(self);
+        Ok(())
+    }
+}
+
+//
+// Crate functions
+//
+
+/// Gets the UCCKPL and UCCKPH bits selecting a SPI mode.
+///
+/// # Returns
+/// The clock polarity and phase bits, in place.
+pub(crate) fn get_clock_mode_bits<Polarity: ClockPolarity, Phase: ClockPhase>() -> u16 {
+    let mut bits = 0;
+
+    if SpiMode::<Polarity, Phase>::is_sampled_on_leading_edge() {
+        bits |= UCCKPH_MASK;
+    }
+
+    if Polarity::get_idle_state() {
+        bits |= UCCKPL_MASK;
+    }
+
+    bits
+}
+
+/// Sets the clock polarity and phase of an eUSCI module in SPI mode. The module is only reset when
+/// the mode changes.
+///
+/// # Arguments
+/// `mode_bits` - Provides the UCCKPL and UCCKPH bits, as returned by `get_clock_mode_bits`.
+pub(crate) fn set_clock_mode<Instance: SpiInstance>(mode_bits: u16) {
+    let regs = get_eusci_registers::<Instance>();
+
+    if (regs.control_0.read() & (UCCKPL_MASK | UCCKPH_MASK)) == mode_bits {
+        return;
+    }
+
+    configure_eusci::<Instance, _>(|regs| {
+        regs.control_0
+            .modify(|value| (value & !(UCCKPL_MASK | UCCKPH_MASK)) | mode_bits);
+    });
+}
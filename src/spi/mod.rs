@@ -2,7 +2,32 @@
 //! The `spi` module includes structures and functions to utilize the Serial Peripheral Interface
 //! (SPI) protocol.
 
+//
+// Internal Modules
+//
+
+mod device;
+mod instance;
+mod master;
+mod slave;
+mod software;
+
+//
+// Reexports
+//
+
+pub use device::*;
+pub use instance::*;
+pub use master::*;
+pub use slave::*;
+pub use software::*;
+
+//
+// Dependencies
+//
+
 use crate::Edge;
+use core::marker::PhantomData;
 
 //
 // Represents different SPI typestate configurations.
@@ -82,8 +107,45 @@ impl ClockPhase for FallingEdgeSample {
 
 /// A zero-sized typestate indicating the SPI instance configuration.
 pub struct SpiMode<Polarity: ClockPolarity, Phase: ClockPhase> {
-    _polarity: Polarity,
-    _phase: Phase,
+    _polarity: PhantomData<Polarity>,
+    _phase: PhantomData<Phase>,
+}
+
+impl<Polarity: ClockPolarity, Phase: ClockPhase> SpiMode<Polarity, Phase> {
+    /// Creates a new SPI mode typestate.
+    ///
+    /// # Returns
+    /// A SPI mode typestate.
+    pub const fn new() -> Self {
+        SpiMode {
+            _polarity: PhantomData,
+            _phase: PhantomData,
+        }
+    }
+
+    /// Checks if data is sampled on the first clock edge after the idle state.
+    ///
+    /// # Returns
+    /// `true` if data is sampled on the leading clock edge.
+    /// `false` if data is sampled on the trailing clock edge.
+    pub fn is_sampled_on_leading_edge() -> bool {
+        // The leading edge rises when the clock idles low, and falls when the clock idles high.
+
+        matches!(Phase::get_sample_edge(), Edge::RisingEdge) != Polarity::get_idle_state()
+    }
+}
+
+impl<Polarity: ClockPolarity, Phase: ClockPhase> Default for SpiMode<Polarity, Phase> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Represents the order in which the bits of a word are shifted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
 }
 
 //
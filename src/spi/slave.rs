@@ -0,0 +1,415 @@
+//! # Slave
+//! The `slave` module includes structures and functions to utilize an eUSCI module as a SPI
+//! slave, exchanging data with the master through interrupt driven buffers.
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{
+        configure_eusci, get_eusci_index, get_eusci_registers, set_eusci_interrupt_handler,
+        EUSCI_MODULE_COUNT, UCOE_MASK, UCRXIE_MASK, UCRXIFG_MASK, UCSWRST_MASK, UCSYNC_MASK,
+        UCTXIE_MASK, UCTXIFG_MASK,
+    },
+    gpio::{set_pin_interrupt_edge, set_pin_interrupt_handler},
+    interrupt::single_proc_critical_section,
+    pin::{AlternatePin, PinId, PinMode, PinWithInterrupt},
+    ringbuffer::RingBuffer,
+    spi::{
+        get_clock_mode_bits, BitOrder, CharacterLength, ClockPhase, ClockPolarity, SpiInstance,
+        SpiMode, SpiPins, UC7BIT_MASK, UCMODE_MASK, UCMODE_SHIFT, UCMSB_MASK,
+    },
+    Edge,
+};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+//
+// Globals
+//
+
+/// State shared between a SPI slave and its interrupt service routine.
+struct SpiSlaveState {
+    /// The characters received from the master.
+    receive: RingBuffer,
+
+    /// The characters queued for the master.
+    transmit: RingBuffer,
+
+    /// The number of characters lost since the last check.
+    overruns: AtomicU32,
+}
+
+/// State of the SPI slave of each eUSCI module.
+static SPI_SLAVE_STATES: [SpiSlaveState; EUSCI_MODULE_COUNT] = [const {
+    SpiSlaveState {
+        receive: RingBuffer::new(),
+        transmit: RingBuffer::new(),
+        overruns: AtomicU32::new(0),
+    }
+}; EUSCI_MODULE_COUNT];
+
+//
+// Structures
+//
+
+/// Represents the level of the STE pin enabling the slave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SteActiveLevel {
+    High = 1,
+    Low = 2,
+}
+
+/// The receive and transmit storage of a SPI slave.
+pub type SpiSlaveStorage = (&'static mut [u8], &'static mut [u8]);
+
+/// Represents an eUSCI module operating as a SPI slave, clocked by the master on its SCLK pin.
+pub struct SpiSlave<Instance: SpiInstance, Polarity: ClockPolarity, Phase: ClockPhase> {
+    /// The eUSCI module.
+    instance: Instance,
+
+    /// The serial clock pin.
+    clock: AlternatePin<Instance::ClockPin>,
+
+    /// The slave in, master out pin.
+    simo: AlternatePin<Instance::SimoPin>,
+
+    /// The slave out, master in pin.
+    somi: AlternatePin<Instance::SomiPin>,
+
+    /// The slave transmit enable pin, in 4-wire operation.
+    ste: Option<AlternatePin<Instance::StePin>>,
+
+    /// The level of the slave transmit enable pin enabling the slave.
+    active_level: SteActiveLevel,
+
+    /// Whether the slave transmit enable pin raises an interrupt at the end of transactions.
+    ste_interrupt_enabled: bool,
+
+    /// The SPI mode.
+    _mode: SpiMode<Polarity, Phase>,
+}
+
+impl<Instance: SpiInstance, Polarity: ClockPolarity, Phase: ClockPhase>
+    SpiSlave<Instance, Polarity, Phase>
+{
+    /// Creates a new SPI slave in 3-wire operation, shifting 8-bit characters MSB first. The
+    /// slave is always enabled, and `dispatch_eusci_interrupt` should be called from the
+    /// interrupt service routine of the module.
+    ///
+    /// # Arguments
+    /// `instance` - Provides the eUSCI module.
+    /// `clock` - Provides the serial clock pin of the module.
+    /// `simo` - Provides the slave in, master out pin of the module.
+    /// `somi` - Provides the slave out, master in pin of the module.
+    /// `mode` - Provides the clock polarity and phase.
+    /// `receive_storage` - Provides the storage for the characters received.
+    /// `transmit_storage` - Provides the storage for the characters queued for the master.
+    ///
+    /// # Returns
+    /// A new SPI slave.
+    pub fn new(
+        instance: Instance,
+        clock: Instance::ClockPin,
+        simo: Instance::SimoPin,
+        somi: Instance::SomiPin,
+        mode: SpiMode<Polarity, Phase>,
+        receive_storage: &'static mut [u8],
+        transmit_storage: &'static mut [u8],
+    ) -> Self {
+        let state = get_state::<Instance>();
+
+        configure_eusci::<Instance, _>(move |regs| {
+            let control = UCMSB_MASK | UCSYNC_MASK | get_clock_mode_bits::<Polarity, Phase>();
+
+            regs.control_0.write(control | UCSWRST_MASK);
+
+            state.receive.attach(receive_storage);
+            state.transmit.attach(transmit_storage);
+            state.overruns.store(0, Ordering::Relaxed);
+        });
+
+        set_eusci_interrupt_handler::<Instance>(Some(service_interrupt::<Instance>));
+        get_eusci_registers::<Instance>()
+            .interrupt_enable
+            .write(UCRXIE_MASK);
+
+        SpiSlave {
+            instance,
+            clock: AlternatePin::new(clock, PinMode::Alternate1),
+            simo: AlternatePin::new(simo, PinMode::Alternate1),
+            somi: AlternatePin::new(somi, PinMode::Alternate1),
+            ste: None,
+            active_level: SteActiveLevel::Low,
+            ste_interrupt_enabled: false,
+            _mode: mode,
+        }
+    }
+
+    /// Switches the slave to 4-wire operation, where the master enables the slave through the STE
+    /// pin. While disabled, the slave ignores the clock and releases the SOMI pin. Characters
+    /// received or queued are discarded.
+    ///
+    /// # Arguments
+    /// `ste` - Provides the slave transmit enable pin of the module.
+    /// `active_level` - Provides the level of the STE pin enabling the slave.
+    ///
+    /// # Returns
+    /// The SPI slave in 4-wire operation.
+    pub fn with_slave_select(
+        mut self,
+        ste: Instance::StePin,
+        active_level: SteActiveLevel,
+    ) -> Self {
+        debug_assert!(self.ste.is_none());
+
+        self.modify_configuration(|control| {
+            (control & !UCMODE_MASK) | ((active_level as u16) << UCMODE_SHIFT)
+        });
+
+        self.ste = Some(AlternatePin::new(ste, PinMode::Alternate1));
+        self.active_level = active_level;
+        self
+    }
+
+    /// Sets the order in which the bits of a character are shifted. Characters received or
+    /// queued are discarded.
+    ///
+    /// # Arguments
+    /// `bit_order` - Provides the bit order.
+    pub fn set_bit_order(&mut self, bit_order: BitOrder) {
+        self.modify_configuration(|control| match bit_order {
+            BitOrder::MsbFirst => control | UCMSB_MASK,
+            BitOrder::LsbFirst => control & !UCMSB_MASK,
+        });
+    }
+
+    /// Sets the number of bits in a character. Characters received or queued are discarded.
+    ///
+    /// # Arguments
+    /// `length` - Provides the character length.
+    pub fn set_character_length(&mut self, length: CharacterLength) {
+        self.modify_configuration(|control| match length {
+            CharacterLength::SevenBit => control | UC7BIT_MASK,
+            CharacterLength::EightBit => control & !UC7BIT_MASK,
+        });
+    }
+
+    /// Takes the characters received from the master.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the characters.
+    ///
+    /// # Returns
+    /// The number of characters placed in the buffer.
+    pub fn read(&mut self, buffer: &mut [u8]) -> usize {
+        let state = get_state::<Instance>();
+        let mut count = 0;
+
+        for byte in buffer.iter_mut() {
+            match state.receive.pop() {
+                Some(received) => *byte = received,
+                None => break,
+            }
+
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Queues characters to send on the next transfers of the master. Should the queue run out,
+    /// the slave repeats its last character.
+    ///
+    /// # Arguments
+    /// `data` - Provides the characters to queue.
+    ///
+    /// # Returns
+    /// The number of characters queued, fewer than provided if the queue is full.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        let state = get_state::<Instance>();
+        let count = data
+            .iter()
+            .take_while(|byte| state.transmit.push(**byte))
+            .count();
+
+        // The interrupt service routine disables the transmit interrupt whenever the queue runs
+        // out, so re-enable it now that the queue holds characters.
+
+        single_proc_critical_section(|_| {
+            get_eusci_registers::<Instance>()
+                .interrupt_enable
+                .set_bits(UCTXIE_MASK);
+        });
+
+        count
+    }
+
+    /// Gets the number of characters received and not yet read.
+    ///
+    /// # Returns
+    /// The number of characters `read` can take.
+    pub fn get_received_count(&self) -> usize {
+        get_state::<Instance>().receive.len()
+    }
+
+    /// Gets the number of characters that can be queued.
+    ///
+    /// # Returns
+    /// The free space of the transmit queue.
+    pub fn get_transmit_space(&self) -> usize {
+        get_state::<Instance>().transmit.get_free_space()
+    }
+
+    /// Gets and resets the number of characters lost because the previous one was not read in
+    /// time, or because the receive storage was full.
+    ///
+    /// # Returns
+    /// The number of overruns since the last call.
+    pub fn take_overruns(&mut self) -> u32 {
+        get_state::<Instance>().overruns.swap(0, Ordering::Relaxed)
+    }
+
+    /// Holds the module in reset and releases its pins and storage.
+    ///
+    /// # Returns
+    /// The eUSCI module, the serial clock, slave in master out and slave out master in pins, the
+    /// slave transmit enable pin if used, and the receive and transmit storage.
+    pub fn extract_parts(
+        self,
+    ) -> (
+        Instance,
+        SpiPins<Instance>,
+        Option<Instance::StePin>,
+        SpiSlaveStorage,
+    ) {
+        let state = get_state::<Instance>();
+
+        get_eusci_registers::<Instance>()
+            .control_0
+            .set_bits(UCSWRST_MASK);
+        set_eusci_interrupt_handler::<Instance>(None);
+
+        let ste_interrupt_enabled = self.ste_interrupt_enabled;
+        let ste = self.ste.map(|ste| {
+            if ste_interrupt_enabled {
+                set_pin_interrupt_edge(ste.get_port_name(), ste.get_offset(), None);
+                set_pin_interrupt_handler(ste.get_port_name(), ste.get_offset(), None);
+            }
+
+            ste.into_pin()
+        });
+
+        (
+            self.instance,
+            (
+                self.clock.into_pin(),
+                self.simo.into_pin(),
+                self.somi.into_pin(),
+            ),
+            ste,
+            (state.receive.detach(), state.transmit.detach()),
+        )
+    }
+
+    /// Modifies the control register, holding the module in reset. The reset discards the
+    /// characters in flight, so the queues are emptied and the interrupts re-enabled.
+    ///
+    /// # Arguments
+    /// `modify_func` - A function returning the new control register value from the current one.
+    fn modify_configuration<F: FnOnce(u16) -> u16>(&mut self, modify_func: F) {
+        let state = get_state::<Instance>();
+
+        configure_eusci::<Instance, _>(|regs| {
+            regs.control_0
+                .modify(|value| modify_func(value) | UCSWRST_MASK);
+
+            while state.receive.pop().is_some() {}
+            while state.transmit.pop().is_some() {}
+        });
+
+        get_eusci_registers::<Instance>()
+            .interrupt_enable
+            .write(UCRXIE_MASK);
+    }
+}
+
+/// The following implements the end of transaction notification for 4-wire slaves whose STE pin
+/// can raise interrupts.
+impl<Instance, Polarity, Phase> SpiSlave<Instance, Polarity, Phase>
+where
+    Instance: SpiInstance,
+    Instance::StePin: PinWithInterrupt,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    /// Registers the handler invoked by `dispatch_port_interrupt` when the master disables the
+    /// slave, marking the end of a transaction. Requires 4-wire operation.
+    ///
+    /// # Arguments
+    /// `handler` - Provides the function to invoke.
+    pub fn set_end_of_transaction_handler(&mut self, handler: fn()) {
+        debug_assert!(self.ste.is_some());
+
+        let Some(ste) = &self.ste else {
+            return;
+        };
+
+        let edge = match self.active_level {
+            SteActiveLevel::High => Edge::FallingEdge,
+            SteActiveLevel::Low => Edge::RisingEdge,
+        };
+
+        set_pin_interrupt_handler(ste.get_port_name(), ste.get_offset(), Some(handler));
+        set_pin_interrupt_edge(ste.get_port_name(), ste.get_offset(), Some(edge));
+        self.ste_interrupt_enabled = true;
+    }
+
+    /// Unregisters the end of transaction handler.
+    pub fn clear_end_of_transaction_handler(&mut self) {
+        if let Some(ste) = &self.ste {
+            set_pin_interrupt_edge(ste.get_port_name(), ste.get_offset(), None);
+            set_pin_interrupt_handler(ste.get_port_name(), ste.get_offset(), None);
+        }
+
+        self.ste_interrupt_enabled = false;
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Gets the state of the SPI slave of an eUSCI module.
+///
+/// # Returns
+/// The state shared with the interrupt service routine.
+fn get_state<Instance: SpiInstance>() -> &'static SpiSlaveState {
+    &SPI_SLAVE_STATES[get_eusci_index::<Instance>()]
+}
+
+/// Services the interrupts of a SPI slave, storing the received characters and feeding the
+/// queued ones.
+fn service_interrupt<Instance: SpiInstance>() {
+    let regs = get_eusci_registers::<Instance>();
+    let state = get_state::<Instance>();
+    let flags = regs.interrupt_flag.read() & regs.interrupt_enable.read();
+
+    if (flags & UCRXIFG_MASK) != 0 {
+        // Reading the receive buffer clears the overrun flag, so check it first.
+
+        let overrun = (regs.status.read() & UCOE_MASK) != 0;
+        let byte = regs.receive_buffer.read() as u8;
+
+        if overrun || !state.receive.push(byte) {
+            state.overruns.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    if (flags & UCTXIFG_MASK) != 0 {
+        match state.transmit.pop() {
+            Some(byte) => regs.transmit_buffer.write(byte as u16),
+            None => regs.interrupt_enable.clear_bits(UCTXIE_MASK),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! # Software
+//! The `software` module includes structures and functions to utilize a SPI master driven by
+//! software over GPIO pins, for when no eUSCI instance is available on the desired pins.
+
+//
+// Dependencies
+//
+
+use crate::{
+    gpio::{GpioPinInput, GpioPinOutput},
+    spi::{BitOrder, ClockPhase, ClockPolarity, SpiMode},
+};
+use embedded_hal::delay::DelayNs;
+
+//
+// Consts
+//
+
+/// Largest word size supported by the software SPI master, in bits.
+pub const SOFTWARE_SPI_MAX_WORD_SIZE: u8 = 16;
+
+//
+// Structures
+//
+
+/// Represents a SPI master that shifts data by toggling GPIO pins.
+pub struct SoftwareSpi<Sck, Mosi, Miso, Delay, Polarity, Phase>
+where
+    Sck: GpioPinOutput,
+    Mosi: GpioPinOutput,
+    Miso: GpioPinInput,
+    Delay: DelayNs,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    /// The serial clock output.
+    sck: Sck,
+
+    /// The master out, slave in data output.
+    mosi: Mosi,
+
+    /// The master in, slave out data input.
+    miso: Miso,
+
+    /// The delay source used to pace the clock.
+    delay: Delay,
+
+    /// The time between two clock edges, in nanoseconds.
+    half_period_ns: u32,
+
+    /// The order in which the bits of a word are shifted.
+    bit_order: BitOrder,
+
+    /// The number of bits in a word.
+    word_size: u8,
+
+    /// The SPI mode.
+    _mode: SpiMode<Polarity, Phase>,
+}
+
+impl<Sck, Mosi, Miso, Delay, Polarity, Phase> SoftwareSpi<Sck, Mosi, Miso, Delay, Polarity, Phase>
+where
+    Sck: GpioPinOutput,
+    Mosi: GpioPinOutput,
+    Miso: GpioPinInput,
+    Delay: DelayNs,
+    Polarity: ClockPolarity,
+    Phase: ClockPhase,
+{
+    /// Creates a new software SPI master, shifting 8-bit words MSB first. The clock is placed in
+    /// its idle state.
+    ///
+    /// # Arguments
+    /// `sck` - Provides the serial clock output pin.
+    /// `mosi` - Provides the data output pin.
+    /// `miso` - Provides the data input pin.
+    /// `delay` - Provides the delay source used to pace the clock.
+    /// `mode` - Provides the clock polarity and phase.
+    /// `frequency_hz` - Provides the desired clock frequency. The actual frequency is lower, as
+    ///   toggling the pins takes time in addition to the delays.
+    ///
+    /// # Returns
+    /// A new software SPI master.
+    pub fn new(
+        mut sck: Sck,
+        mosi: Mosi,
+        miso: Miso,
+        delay: Delay,
+        mode: SpiMode<Polarity, Phase>,
+        frequency_hz: u32,
+    ) -> Self {
+        debug_assert!(frequency_hz > 0);
+
+        write_pin(&mut sck, Polarity::get_idle_state());
+
+        SoftwareSpi {
+            sck,
+            mosi,
+            miso,
+            delay,
+            half_period_ns: 500_000_000 / frequency_hz,
+            bit_order: BitOrder::MsbFirst,
+            word_size: 8,
+            _mode: mode,
+        }
+    }
+
+    /// Sets the order in which the bits of a word are shifted.
+    ///
+    /// # Arguments
+    /// `bit_order` - Provides the bit order.
+    pub fn set_bit_order(&mut self, bit_order: BitOrder) {
+        self.bit_order = bit_order;
+    }
+
+    /// Sets the number of bits in a word.
+    ///
+    /// # Arguments
+    /// `word_size` - Provides the word size, between 1 and `SOFTWARE_SPI_MAX_WORD_SIZE` bits.
+    pub fn set_word_size(&mut self, word_size: u8) {
+        debug_assert!((1..=SOFTWARE_SPI_MAX_WORD_SIZE).contains(&word_size));
+        self.word_size = word_size;
+    }
+
+    /// Sets the clock frequency.
+    ///
+    /// # Arguments
+    /// `frequency_hz` - Provides the desired clock frequency.
+    pub fn set_frequency(&mut self, frequency_hz: u32) {
+        debug_assert!(frequency_hz > 0);
+        self.half_period_ns = 500_000_000 / frequency_hz;
+    }
+
+    /// Simultaneously sends and receives a word.
+    ///
+    /// # Arguments
+    /// `word` - Provides the word to send. Bits above the word size are ignored.
+    ///
+    /// # Returns
+    /// The received word.
+    pub fn transfer_word(&mut self, word: u16) -> u16 {
+        let mut received = 0;
+
+        for index in 0..self.word_size {
+            let bit = match self.bit_order {
+                BitOrder::MsbFirst => self.word_size - 1 - index,
+                BitOrder::LsbFirst => index,
+            };
+
+            if self.transfer_bit((word >> bit) & 1 != 0) {
+                received |= 1 << bit;
+            }
+        }
+
+        received
+    }
+
+    /// Simultaneously sends and receives words, replacing each sent word with the received word.
+    ///
+    /// # Arguments
+    /// `words` - Provides the words to send, and receives the read words.
+    pub fn transfer_in_place(&mut self, words: &mut [u16]) {
+        for word in words.iter_mut() {
+            *word = self.transfer_word(*word);
+        }
+    }
+
+    /// Sends words, discarding the received words.
+    ///
+    /// # Arguments
+    /// `words` - Provides the words to send.
+    pub fn write(&mut self, words: &[u16]) {
+        for word in words {
+            self.transfer_word(*word);
+        }
+    }
+
+    /// Receives words, sending zeros.
+    ///
+    /// # Arguments
+    /// `words` - Receives the read words.
+    pub fn read(&mut self, words: &mut [u16]) {
+        for word in words.iter_mut() {
+            *word = self.transfer_word(0);
+        }
+    }
+
+    /// Extracts the pins and the delay source, leaving the clock in its idle state.
+    ///
+    /// # Returns
+    /// The clock, data output and data input pins, and the delay source.
+    pub fn extract_parts(self) -> (Sck, Mosi, Miso, Delay) {
+        (self.sck, self.mosi, self.miso, self.delay)
+    }
+
+    /// Shifts a single bit out and in, over a full clock period.
+    ///
+    /// # Arguments
+    /// `bit` - Provides the bit to send.
+    ///
+    /// # Returns
+    /// The received bit.
+    fn transfer_bit(&mut self, bit: bool) -> bool {
+        let idle_state = Polarity::get_idle_state();
+
+        // When sampling on the leading edge, the data is set up during the idle state. Otherwise,
+        // the data is shifted out on the leading edge and sampled on the trailing edge.
+
+        if SpiMode::<Polarity, Phase>::is_sampled_on_leading_edge() {
+            write_pin(&mut self.mosi, bit);
+            self.delay.delay_ns(self.half_period_ns);
+            write_pin(&mut self.sck, !idle_state);
+            let received = self.miso.read();
+            self.delay.delay_ns(self.half_period_ns);
+            write_pin(&mut self.sck, idle_state);
+            received
+        } else {
+            write_pin(&mut self.sck, !idle_state);
+            write_pin(&mut self.mosi, bit);
+            self.delay.delay_ns(self.half_period_ns);
+            write_pin(&mut self.sck, idle_state);
+            let received = self.miso.read();
+            self.delay.delay_ns(self.half_period_ns);
+            received
+        }
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Drives an output pin to a given level.
+///
+/// # Arguments
+/// `pin` - Provides the pin to drive.
+/// `level` - Provides the level, `true` for high.
+fn write_pin<Pin: GpioPinOutput>(pin: &mut Pin, level: bool) {
+    if level {
+        pin.set();
+    } else {
+        pin.clear();
+    }
+}
@@ -0,0 +1,216 @@
+//! # Baud Rate
+//! The `baudrate` module includes structures and functions to compute the eUSCI_A baud rate
+//! generator settings for a given clock frequency and baud rate.
+
+//
+// Consts
+//
+
+/// Fractional parts of the clock cycles per bit, in 1/10000, and the UCBRSx second modulation
+/// stage patterns best suited to them, as provided by the eUSCI_A documentation.
+const SECOND_MODULATION_TABLE: [(u32, u8); 36] = [
+    (0, 0x00),
+    (529, 0x01),
+    (715, 0x02),
+    (835, 0x04),
+    (1001, 0x08),
+    (1252, 0x10),
+    (1430, 0x20),
+    (1670, 0x11),
+    (2147, 0x21),
+    (2224, 0x22),
+    (2503, 0x44),
+    (3000, 0x25),
+    (3335, 0x49),
+    (3575, 0x4A),
+    (3753, 0x52),
+    (4003, 0x92),
+    (4286, 0x53),
+    (4378, 0x55),
+    (5002, 0xAA),
+    (5715, 0x6B),
+    (6003, 0xAD),
+    (6254, 0xB5),
+    (6432, 0xB6),
+    (6667, 0xD6),
+    (7001, 0xB7),
+    (7147, 0xBB),
+    (7503, 0xDD),
+    (7861, 0xED),
+    (8004, 0xEE),
+    (8333, 0xBF),
+    (8464, 0xDF),
+    (8572, 0xEF),
+    (8751, 0xF7),
+    (9004, 0xFB),
+    (9170, 0xFD),
+    (9288, 0xFE),
+];
+
+/// Number of clock cycles per bit from which oversampling is used.
+const OVERSAMPLING_THRESHOLD: u32 = 16;
+
+const UCOS16_MASK: u16 = 1 << 0;
+const UCBRF_SHIFT: u16 = 4;
+const UCBRS_SHIFT: u16 = 8;
+
+//
+// Structures
+//
+
+/// Represents the settings of the eUSCI_A baud rate generator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UartBaudRate {
+    /// The clock prescaler (UCBRx).
+    prescaler: u16,
+
+    /// The first modulation stage (UCBRFx), only used with oversampling.
+    first_modulation: u8,
+
+    /// The second modulation stage pattern (UCBRSx).
+    second_modulation: u8,
+
+    /// Whether the receiver samples each bit 16 times (UCOS16).
+    oversampling: bool,
+
+    /// The average baud rate produced by the settings.
+    actual_baud_rate: u32,
+
+    /// The error of the average baud rate relative to the requested one, in parts per million.
+    error_ppm: i32,
+}
+
+impl UartBaudRate {
+    /// Computes the baud rate generator settings, following the eUSCI_A documentation.
+    ///
+    /// # Arguments
+    /// `clock_hz` - Provides the frequency of the clock feeding the module.
+    /// `baud_rate` - Provides the requested baud rate, no higher than the clock frequency.
+    ///
+    /// # Returns
+    /// The baud rate generator settings.
+    pub fn calculate(clock_hz: u32, baud_rate: u32) -> Self {
+        debug_assert!(baud_rate > 0 && baud_rate <= clock_hz);
+
+        let cycles = clock_hz / baud_rate;
+        let fraction = ((clock_hz % baud_rate) as u64 * 10_000 / baud_rate as u64) as u32;
+
+        let second_modulation = SECOND_MODULATION_TABLE
+            .iter()
+            .rev()
+            .find(|(threshold, _)| *threshold <= fraction)
+            .map_or(0, |(_, pattern)| *pattern);
+
+        let (prescaler, first_modulation, oversampling) = if cycles >= OVERSAMPLING_THRESHOLD {
+            let oversampled_rate = baud_rate * OVERSAMPLING_THRESHOLD;
+
+            (
+                clock_hz / oversampled_rate,
+                ((clock_hz % oversampled_rate) / baud_rate) as u8,
+                true,
+            )
+        } else {
+            (cycles.max(1), 0, false)
+        };
+
+        let prescaler = prescaler.min(u16::MAX as u32);
+
+        // Each set bit of the second modulation pattern stretches one bit out of eight by a clock
+        // cycle, so the average bit lasts that many eighths of a cycle longer.
+
+        let whole_cycles = if oversampling {
+            prescaler * OVERSAMPLING_THRESHOLD + first_modulation as u32
+        } else {
+            prescaler
+        };
+
+        let eighth_cycles = whole_cycles as u64 * 8 + second_modulation.count_ones() as u64;
+        let actual_baud_rate = (clock_hz as u64 * 8 / eighth_cycles) as u32;
+        let error_ppm = (actual_baud_rate as i64 - baud_rate as i64) * 1_000_000 / baud_rate as i64;
+
+        UartBaudRate {
+            prescaler: prescaler as u16,
+            first_modulation,
+            second_modulation,
+            oversampling,
+            actual_baud_rate,
+            error_ppm: error_ppm as i32,
+        }
+    }
+
+    /// Gets the average baud rate produced by the settings.
+    ///
+    /// # Returns
+    /// Baud rate in bits per second.
+    pub fn get_actual_baud_rate(&self) -> u32 {
+        self.actual_baud_rate
+    }
+
+    /// Gets the error of the average baud rate relative to the requested one. Individual bits may
+    /// deviate further, by up to a clock cycle.
+    ///
+    /// # Returns
+    /// Signed error in parts per million, positive when the actual baud rate is higher.
+    pub fn get_error_ppm(&self) -> i32 {
+        self.error_ppm
+    }
+
+    /// Checks if the receiver samples each bit 16 times.
+    ///
+    /// # Returns
+    /// `true` if oversampling is used.
+    /// `false` otherwise.
+    pub fn is_oversampling(&self) -> bool {
+        self.oversampling
+    }
+
+    /// Gets the clock prescaler, for the baud rate control register.
+    ///
+    /// # Returns
+    /// The UCBRx value.
+    pub(crate) fn get_prescaler(&self) -> u16 {
+        self.prescaler
+    }
+
+    /// Gets the modulation settings, for the modulation control register.
+    ///
+    /// # Returns
+    /// The UCBRSx, UCBRFx and UCOS16 bits, in place.
+    pub(crate) fn get_modulation_bits(&self) -> u16 {
+        let mut bits = ((self.second_modulation as u16) << UCBRS_SHIFT)
+            | ((self.first_modulation as u16) << UCBRF_SHIFT);
+
+        if self.oversampling {
+            bits |= UCOS16_MASK;
+        }
+
+        bits
+    }
+}
+
+//
+// Crate functions
+//
+
+/// Computes the average baud rate produced by the baud rate generator registers, such as after the
+/// module measured the baud rate itself.
+///
+/// # Arguments
+/// `clock_hz` - Provides the frequency of the clock feeding the module.
+/// `prescaler` - Provides the baud rate control register.
+/// `modulation` - Provides the modulation control register.
+///
+/// # Returns
+/// Baud rate in bits per second.
+pub(crate) fn get_register_baud_rate(clock_hz: u32, prescaler: u16, modulation: u16) -> u32 {
+    let whole_cycles = if (modulation & UCOS16_MASK) != 0 {
+        prescaler as u32 * OVERSAMPLING_THRESHOLD + ((modulation >> UCBRF_SHIFT) & 0xF) as u32
+    } else {
+        prescaler as u32
+    };
+
+    let second_modulation = (modulation >> UCBRS_SHIFT) as u8;
+    let eighth_cycles = whole_cycles as u64 * 8 + second_modulation.count_ones() as u64;
+
+    (clock_hz as u64 * 8 / eighth_cycles.max(1)) as u32
+}
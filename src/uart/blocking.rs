@@ -0,0 +1,288 @@
+//! # Blocking
+//! The `blocking` module includes structures and functions to utilize an eUSCI_A module as a UART
+//! that waits on the module for every character.
+
+//
+// Dependencies
+//
+
+use crate::{
+    clock::ClockFrequencies,
+    eusci::{
+        configure_eusci, get_eusci_a_registers, UCBUSY_MASK, UCRXIFG_MASK, UCSWRST_MASK,
+        UCTXIFG_MASK,
+    },
+    pin::{AlternatePin, PinMode},
+    uart::{
+        get_receive_error, MultiprocessorMode, UartBaudRate, UartConfig, UartError, UartInstance,
+        UCADDR_MASK, UCDORM_MASK, UCMODE_MASK, UCMODE_SHIFT, UCRXEIE_MASK, UCTXADDR_MASK,
+    },
+};
+use core::fmt;
+
+//
+// Structures
+//
+
+/// Represents an eUSCI_A module operating as a UART.
+pub struct Uart<Instance: UartInstance> {
+    /// The eUSCI_A module.
+    instance: Instance,
+
+    /// The transmit pin.
+    tx: AlternatePin<Instance::TxPin>,
+
+    /// The receive pin.
+    rx: AlternatePin<Instance::RxPin>,
+
+    /// The configuration.
+    config: UartConfig,
+
+    /// The baud rate generator settings.
+    baud_rate: UartBaudRate,
+}
+
+impl<Instance: UartInstance> Uart<Instance> {
+    /// Creates a new UART.
+    ///
+    /// # Arguments
+    /// `instance` - Provides the eUSCI_A module.
+    /// `tx` - Provides the transmit pin of the module.
+    /// `rx` - Provides the receive pin of the module.
+    /// `config` - Provides the character format, baud rate and clock source.
+    /// `clocks` - Provides the clock frequencies.
+    ///
+    /// # Returns
+    /// A new UART. The baud rate error can be checked with `get_baud_rate`.
+    pub fn new(
+        instance: Instance,
+        tx: Instance::TxPin,
+        rx: Instance::RxPin,
+        config: &UartConfig,
+        clocks: &ClockFrequencies,
+    ) -> Self {
+        let baud_rate =
+            UartBaudRate::calculate(config.clock_source.get_frequency(clocks), config.baud_rate);
+
+        configure_eusci::<Instance, _>(|_| {
+            let regs = get_eusci_a_registers::<Instance>();
+
+            // Erroneous characters are received as well, so the errors can be reported.
+
+            regs.control_0
+                .write(config.get_control_bits() | UCRXEIE_MASK | UCSWRST_MASK);
+            regs.baud_rate.write(baud_rate.get_prescaler());
+            regs.modulation_control
+                .write(baud_rate.get_modulation_bits());
+            regs.interrupt_enable.write(0);
+        });
+
+        Uart {
+            instance,
+            tx: AlternatePin::new(tx, PinMode::Alternate1),
+            rx: AlternatePin::new(rx, PinMode::Alternate1),
+            config: *config,
+            baud_rate,
+        }
+    }
+
+    /// Sets the baud rate. Characters in flight are lost.
+    ///
+    /// # Arguments
+    /// `baud_rate` - Provides the requested baud rate.
+    /// `clocks` - Provides the clock frequencies.
+    pub fn set_baud_rate(&mut self, baud_rate: u32, clocks: &ClockFrequencies) {
+        self.config.baud_rate = baud_rate;
+        self.baud_rate =
+            UartBaudRate::calculate(self.config.clock_source.get_frequency(clocks), baud_rate);
+
+        configure_eusci::<Instance, _>(|_| {
+            let regs = get_eusci_a_registers::<Instance>();
+
+            regs.baud_rate.write(self.baud_rate.get_prescaler());
+            regs.modulation_control
+                .write(self.baud_rate.get_modulation_bits());
+        });
+    }
+
+    /// Gets the baud rate generator settings, including the actual baud rate and its error.
+    ///
+    /// # Returns
+    /// The baud rate generator settings.
+    pub fn get_baud_rate(&self) -> &UartBaudRate {
+        &self.baud_rate
+    }
+
+    /// Gets the configuration.
+    ///
+    /// # Returns
+    /// The configuration.
+    pub fn get_config(&self) -> &UartConfig {
+        &self.config
+    }
+
+    /// Sets the multiprocessor format. Characters in flight are lost.
+    ///
+    /// # Arguments
+    /// `mode` - Provides the multiprocessor format.
+    pub fn set_multiprocessor_mode(&mut self, mode: MultiprocessorMode) {
+        configure_eusci::<Instance, _>(|regs| {
+            regs.control_0
+                .modify(|value| (value & !UCMODE_MASK) | ((mode as u16) << UCMODE_SHIFT));
+        });
+    }
+
+    /// Sets whether the receiver sleeps until an address is received. Only addresses are received
+    /// while dormant, so the receiver should wake up once its own address is received.
+    ///
+    /// # Arguments
+    /// `dormant` - Provides whether only addresses are received.
+    pub fn set_dormant(&mut self, dormant: bool) {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        if dormant {
+            regs.control_0.set_bits(UCDORM_MASK);
+        } else {
+            regs.control_0.clear_bits(UCDORM_MASK);
+        }
+    }
+
+    /// Sends an address in the multiprocessor format. In idle-line format, the address is
+    /// preceded by an idle line. In address-bit format, the address bit of the character is set.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address to send.
+    pub fn write_address(&mut self, address: u8) {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        while (regs.interrupt_flag.read() & UCTXIFG_MASK) == 0 {}
+
+        // The module clears the address flag once it starts sending the character.
+
+        regs.control_0.set_bits(UCTXADDR_MASK);
+        regs.transmit_buffer.write(address as u16);
+    }
+
+    /// Sends a character, waiting for room in the transmit buffer.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the character to send.
+    pub fn write_byte(&mut self, byte: u8) {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        while (regs.interrupt_flag.read() & UCTXIFG_MASK) == 0 {}
+        regs.transmit_buffer.write(byte as u16);
+    }
+
+    /// Sends characters.
+    ///
+    /// # Arguments
+    /// `data` - Provides the characters to send.
+    pub fn write(&mut self, data: &[u8]) {
+        for byte in data {
+            self.write_byte(*byte);
+        }
+    }
+
+    /// Waits until the module is done transmitting and receiving.
+    pub fn flush(&mut self) {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        while (regs.interrupt_flag.read() & UCTXIFG_MASK) == 0 {}
+        while (regs.status.read() & UCBUSY_MASK) != 0 {}
+    }
+
+    /// Receives a character if one is available.
+    ///
+    /// # Returns
+    /// `Some(Ok(byte))` if a character was received.
+    /// `Some(Err(error))` if a character was received with an error.
+    /// `None` if no character is available.
+    pub fn try_read_byte(&mut self) -> Option<Result<u8, UartError>> {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        if (regs.interrupt_flag.read() & UCRXIFG_MASK) == 0 {
+            return None;
+        }
+
+        // Reading the receive buffer clears the error flags, so check them first.
+
+        let status = regs.status.read();
+        let byte = regs.receive_buffer.read() as u8;
+
+        Some(match get_receive_error(status) {
+            Some(error) => Err(error),
+            None => Ok(byte),
+        })
+    }
+
+    /// Receives a character in the multiprocessor format, waiting for one to be available.
+    ///
+    /// # Returns
+    /// `Ok((byte, true))` if an address was received.
+    /// `Ok((byte, false))` if data was received.
+    /// `Err(error)` if a character was received with an error.
+    pub fn read_addressed_byte(&mut self) -> Result<(u8, bool), UartError> {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        while (regs.interrupt_flag.read() & UCRXIFG_MASK) == 0 {}
+
+        let status = regs.status.read();
+        let byte = regs.receive_buffer.read() as u8;
+
+        match get_receive_error(status) {
+            Some(error) => Err(error),
+            None => Ok((byte, (status & UCADDR_MASK) != 0)),
+        }
+    }
+
+    /// Receives a character, waiting for one to be available.
+    ///
+    /// # Returns
+    /// `Ok(byte)` if a character was received.
+    /// `Err(error)` if a character was received with an error.
+    pub fn read_byte(&mut self) -> Result<u8, UartError> {
+        loop {
+            if let Some(result) = self.try_read_byte() {
+                return result;
+            }
+        }
+    }
+
+    /// Receives characters, waiting for the buffer to be filled. Stops at the first character
+    /// received with an error.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the characters.
+    ///
+    /// # Returns
+    /// `Ok(())` if the buffer was filled.
+    /// `Err(error)` if a character was received with an error.
+    pub fn read(&mut self, buffer: &mut [u8]) -> Result<(), UartError> {
+        for byte in buffer.iter_mut() {
+            *byte = self.read_byte()?;
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the module to be done transmitting, holds it in reset and releases its pins.
+    ///
+    /// # Returns
+    /// The eUSCI_A module, and the transmit and receive pins.
+    pub fn extract_parts(mut self) -> (Instance, Instance::TxPin, Instance::RxPin) {
+        self.flush();
+        get_eusci_a_registers::<Instance>()
+            .control_0
+            .set_bits(UCSWRST_MASK);
+
+        (self.instance, self.tx.into_pin(), self.rx.into_pin())
+    }
+}
+
+impl<Instance: UartInstance> fmt::Write for Uart<Instance> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
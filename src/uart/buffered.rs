@@ -0,0 +1,574 @@
+//! # Buffered
+//! The `buffered` module includes structures and functions to utilize an eUSCI_A module as a UART
+//! whose characters are moved between ring buffers and the module by its interrupt service
+//! routine.
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{
+        get_eusci_a_registers, get_eusci_index, set_eusci_interrupt_handler, EUSCI_MODULE_COUNT,
+        UCBUSY_MASK, UCRXIE_MASK, UCRXIFG_MASK, UCTXIE_MASK, UCTXIFG_MASK,
+    },
+    gpio::{
+        read_pin_input, write_pin_output, GpioIn, GpioInputMode, GpioOut, GpioPin, GpioPushPullMode,
+    },
+    interrupt::single_proc_critical_section,
+    pin::{PinId, PinWithInterrupt, PinX},
+    ringbuffer::RingBuffer,
+    uart::{get_receive_error, Uart, UartError, UartInstance},
+    Edge,
+};
+use core::{
+    convert::Infallible,
+    sync::atomic::{AtomicU16, AtomicU32, Ordering},
+};
+use embedded_io::{ErrorType, Read, ReadReady, Write, WriteReady};
+
+//
+// Consts
+//
+
+/// Location of a flow control pin that is not used.
+const NO_PIN: u16 = u16::MAX;
+
+/// Free space of the receive buffer below which the sender is asked to pause. Leaves room for
+/// the characters the sender has already committed to.
+const RTS_HEADROOM: usize = 4;
+
+//
+// Traits
+//
+
+/// A request to send (RTS) output, driven low while the UART can receive.
+pub trait RtsPin: private::RtsPinAccess {}
+
+/// A clear to send (CTS) input, driven low while the UART may transmit.
+pub trait CtsPin: private::CtsPinAccess {}
+
+//
+// Globals
+//
+
+/// State shared between a buffered UART and its interrupt service routine.
+struct BufferedUartState {
+    /// The characters received.
+    receive: RingBuffer,
+
+    /// The characters queued for transmission.
+    transmit: RingBuffer,
+
+    /// The number of characters lost because the previous one was not read in time, or because
+    /// the receive buffer was full.
+    overruns: AtomicU32,
+
+    /// The number of characters discarded because of a low stop bit.
+    framing_errors: AtomicU32,
+
+    /// The number of characters discarded because of a parity mismatch.
+    parity_errors: AtomicU32,
+
+    /// The location of the RTS output.
+    rts: AtomicU16,
+
+    /// The location of the CTS input.
+    cts: AtomicU16,
+}
+
+/// State of the buffered UART of each eUSCI module.
+static BUFFERED_UART_STATES: [BufferedUartState; EUSCI_MODULE_COUNT] = [const {
+    BufferedUartState {
+        receive: RingBuffer::new(),
+        transmit: RingBuffer::new(),
+        overruns: AtomicU32::new(0),
+        framing_errors: AtomicU32::new(0),
+        parity_errors: AtomicU32::new(0),
+        rts: AtomicU16::new(NO_PIN),
+        cts: AtomicU16::new(NO_PIN),
+    }
+}; EUSCI_MODULE_COUNT];
+
+//
+// Structures
+//
+
+/// A zero-sized typestate indicating an unused flow control pin.
+pub struct NoFlowControl;
+impl RtsPin for NoFlowControl {}
+impl CtsPin for NoFlowControl {}
+
+impl<Pin: PinX, OutputMode: GpioPushPullMode> RtsPin for GpioPin<Pin, GpioOut<OutputMode>> {}
+impl<Pin: PinWithInterrupt, InputMode: GpioInputMode> CtsPin for GpioPin<Pin, GpioIn<InputMode>> {}
+
+/// Represents the number of receive errors of a buffered UART.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UartErrorCounts {
+    /// The number of characters lost because the previous one was not read in time, or because
+    /// the receive buffer was full.
+    pub overruns: u32,
+
+    /// The number of characters discarded because of a low stop bit.
+    pub framing_errors: u32,
+
+    /// The number of characters discarded because of a parity mismatch.
+    pub parity_errors: u32,
+}
+
+/// The receive and transmit storage of a buffered UART.
+pub type BufferedUartStorage = (&'static mut [u8], &'static mut [u8]);
+
+/// Represents an eUSCI_A module operating as a UART, whose interrupt service routine moves the
+/// characters between the module and ring buffers.
+pub struct BufferedUart<Instance: UartInstance, Rts: RtsPin, Cts: CtsPin> {
+    /// The UART.
+    uart: Uart<Instance>,
+
+    /// The request to send output.
+    rts: Rts,
+
+    /// The clear to send input.
+    cts: Cts,
+}
+
+impl<Instance: UartInstance, Rts: RtsPin, Cts: CtsPin> BufferedUart<Instance, Rts, Cts> {
+    /// Creates a new buffered UART. `dispatch_eusci_interrupt` should be called from the interrupt
+    /// service routine of the module, and `dispatch_port_interrupt` from the one of the port of
+    /// the CTS input.
+    ///
+    /// # Arguments
+    /// `uart` - Provides the UART.
+    /// `receive_storage` - Provides the storage for the characters received.
+    /// `transmit_storage` - Provides the storage for the characters queued for transmission.
+    /// `rts` - Provides the request to send output, or `NoFlowControl`.
+    /// `cts` - Provides the clear to send input, or `NoFlowControl`.
+    ///
+    /// # Returns
+    /// A new buffered UART.
+    pub fn new(
+        mut uart: Uart<Instance>,
+        receive_storage: &'static mut [u8],
+        transmit_storage: &'static mut [u8],
+        rts: Rts,
+        mut cts: Cts,
+    ) -> Self {
+        let state = get_state::<Instance>();
+
+        uart.flush();
+
+        state.receive.attach(receive_storage);
+        state.transmit.attach(transmit_storage);
+        state.overruns.store(0, Ordering::Relaxed);
+        state.framing_errors.store(0, Ordering::Relaxed);
+        state.parity_errors.store(0, Ordering::Relaxed);
+        state
+            .rts
+            .store(private::RtsPinAccess::get_location(&rts), Ordering::Relaxed);
+        state
+            .cts
+            .store(private::CtsPinAccess::get_location(&cts), Ordering::Relaxed);
+
+        // Ready to receive.
+
+        set_pin(state.rts.load(Ordering::Relaxed), false);
+        cts.enable_resume(resume_transmission::<Instance>);
+
+        set_eusci_interrupt_handler::<Instance>(Some(service_interrupt::<Instance>));
+        get_eusci_a_registers::<Instance>()
+            .interrupt_enable
+            .set_bits(UCRXIE_MASK);
+
+        BufferedUart { uart, rts, cts }
+    }
+
+    /// Takes the characters received, without waiting.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the characters.
+    ///
+    /// # Returns
+    /// The number of characters placed in the buffer.
+    pub fn try_read(&mut self, buffer: &mut [u8]) -> usize {
+        let state = get_state::<Instance>();
+        let mut count = 0;
+
+        for byte in buffer.iter_mut() {
+            match state.receive.pop() {
+                Some(received) => *byte = received,
+                None => break,
+            }
+
+            count += 1;
+        }
+
+        // The interrupt service routine asks the sender to pause when the buffer fills up, so ask
+        // it to resume once there is room again.
+
+        single_proc_critical_section(|_| {
+            if state.receive.get_free_space() > RTS_HEADROOM {
+                set_pin(state.rts.load(Ordering::Relaxed), false);
+            }
+        });
+
+        count
+    }
+
+    /// Queues characters for transmission, without waiting.
+    ///
+    /// # Arguments
+    /// `data` - Provides the characters to queue.
+    ///
+    /// # Returns
+    /// The number of characters queued, fewer than provided if the transmit buffer is full.
+    pub fn try_write(&mut self, data: &[u8]) -> usize {
+        let state = get_state::<Instance>();
+        let count = data
+            .iter()
+            .take_while(|byte| state.transmit.push(**byte))
+            .count();
+
+        resume_transmission::<Instance>();
+        count
+    }
+
+    /// Gets the number of characters received and not yet read.
+    ///
+    /// # Returns
+    /// The number of characters `try_read` can take.
+    pub fn get_received_count(&self) -> usize {
+        get_state::<Instance>().receive.len()
+    }
+
+    /// Gets the number of characters that can be queued for transmission.
+    ///
+    /// # Returns
+    /// The free space of the transmit buffer.
+    pub fn get_transmit_space(&self) -> usize {
+        get_state::<Instance>().transmit.get_free_space()
+    }
+
+    /// Gets and resets the number of receive errors.
+    ///
+    /// # Returns
+    /// The number of receive errors since the last call.
+    pub fn take_error_counts(&mut self) -> UartErrorCounts {
+        let state = get_state::<Instance>();
+
+        UartErrorCounts {
+            overruns: state.overruns.swap(0, Ordering::Relaxed),
+            framing_errors: state.framing_errors.swap(0, Ordering::Relaxed),
+            parity_errors: state.parity_errors.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    /// Waits until the queued characters are transmitted.
+    pub fn flush(&mut self) {
+        let state = get_state::<Instance>();
+
+        while !state.transmit.is_empty() {}
+        while (get_eusci_a_registers::<Instance>().status.read() & UCBUSY_MASK) != 0 {}
+    }
+
+    /// Waits for the queued characters to be transmitted, then switches back to blocking
+    /// operation. Characters received and not yet read are discarded.
+    ///
+    /// # Returns
+    /// The UART, the flow control pins, and the receive and transmit storage.
+    pub fn into_blocking(mut self) -> (Uart<Instance>, Rts, Cts, BufferedUartStorage) {
+        let state = get_state::<Instance>();
+
+        self.flush();
+
+        get_eusci_a_registers::<Instance>()
+            .interrupt_enable
+            .clear_bits(UCRXIE_MASK | UCTXIE_MASK);
+        set_eusci_interrupt_handler::<Instance>(None);
+        self.cts.disable_resume();
+
+        state.rts.store(NO_PIN, Ordering::Relaxed);
+        state.cts.store(NO_PIN, Ordering::Relaxed);
+
+        (
+            self.uart,
+            self.rts,
+            self.cts,
+            (state.receive.detach(), state.transmit.detach()),
+        )
+    }
+}
+
+//
+// embedded-io traits.
+//
+
+impl<Instance: UartInstance, Rts: RtsPin, Cts: CtsPin> ErrorType
+    for BufferedUart<Instance, Rts, Cts>
+{
+    type Error = Infallible;
+}
+
+impl<Instance: UartInstance, Rts: RtsPin, Cts: CtsPin> Read for BufferedUart<Instance, Rts, Cts> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            let count = self.try_read(buf);
+            if count > 0 {
+                return Ok(count);
+            }
+        }
+    }
+}
+
+impl<Instance: UartInstance, Rts: RtsPin, Cts: CtsPin> ReadReady
+    for BufferedUart<Instance, Rts, Cts>
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.get_received_count() > 0)
+    }
+}
+
+impl<Instance: UartInstance, Rts: RtsPin, Cts: CtsPin> Write for BufferedUart<Instance, Rts, Cts> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            let count = self.try_write(buf);
+            if count > 0 {
+                return Ok(count);
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        BufferedUart::flush(self);
+        Ok(())
+    }
+}
+
+impl<Instance: UartInstance, Rts: RtsPin, Cts: CtsPin> WriteReady
+    for BufferedUart<Instance, Rts, Cts>
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.get_transmit_space() > 0)
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Gets the state of the buffered UART of an eUSCI module.
+///
+/// # Returns
+/// The state shared with the interrupt service routine.
+fn get_state<Instance: UartInstance>() -> &'static BufferedUartState {
+    &BUFFERED_UART_STATES[get_eusci_index::<Instance>()]
+}
+
+/// Packs the location of a pin.
+///
+/// # Arguments
+/// `pin` - Provides the pin.
+///
+/// # Returns
+/// The port name in the upper byte, and the offset in the lower byte.
+fn pack_location<Pin: PinId>(pin: &Pin) -> u16 {
+    ((pin.get_port_name() as u16) << 8) | (pin.get_offset() as u16)
+}
+
+/// Drives a flow control output.
+///
+/// # Arguments
+/// `location` - Provides the location of the pin, or `NO_PIN`.
+/// `level` - Provides the level, `true` for high.
+fn set_pin(location: u16, level: bool) {
+    if location != NO_PIN {
+        write_pin_output((location >> 8) as u8 as char, location as u8, level);
+    }
+}
+
+/// Checks if a flow control input pauses the transmission.
+///
+/// # Arguments
+/// `location` - Provides the location of the pin, or `NO_PIN`.
+///
+/// # Returns
+/// `true` if the pin is high.
+/// `false` if the pin is low or not used.
+fn is_pin_high(location: u16) -> bool {
+    location != NO_PIN && read_pin_input((location >> 8) as u8 as char, location as u8)
+}
+
+/// Enables the transmit interrupt if characters are queued and the receiver is clear to receive.
+/// Invoked after queuing characters, and when the CTS input is driven low.
+fn resume_transmission<Instance: UartInstance>() {
+    let state = get_state::<Instance>();
+
+    if state.transmit.is_empty() {
+        return;
+    }
+
+    single_proc_critical_section(|_| {
+        get_eusci_a_registers::<Instance>()
+            .interrupt_enable
+            .set_bits(UCTXIE_MASK);
+    });
+}
+
+/// Services the interrupts of a buffered UART, storing the received characters and transmitting
+/// the queued ones.
+fn service_interrupt<Instance: UartInstance>() {
+    let regs = get_eusci_a_registers::<Instance>();
+    let state = get_state::<Instance>();
+    let flags = regs.interrupt_flag.read() & regs.interrupt_enable.read();
+
+    if (flags & UCRXIFG_MASK) != 0 {
+        // Reading the receive buffer clears the error flags, so check them first.
+
+        let status = regs.status.read();
+        let byte = regs.receive_buffer.read() as u8;
+
+        let keep = match get_receive_error(status) {
+            Some(UartError::Overrun) => {
+                state.overruns.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+
+            Some(UartError::Framing) => {
+                state.framing_errors.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+
+            Some(UartError::Parity) => {
+                state.parity_errors.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+
+            None => true,
+        };
+
+        if keep && !state.receive.push(byte) {
+            state.overruns.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if state.receive.get_free_space() <= RTS_HEADROOM {
+            set_pin(state.rts.load(Ordering::Relaxed), true);
+        }
+    }
+
+    if (flags & UCTXIFG_MASK) != 0 {
+        // Transmission pauses when the queue runs out or the receiver is not clear to receive,
+        // until `resume_transmission` enables the interrupt again.
+
+        let byte = if is_pin_high(state.cts.load(Ordering::Relaxed)) {
+            None
+        } else {
+            state.transmit.pop()
+        };
+
+        match byte {
+            Some(byte) => regs.transmit_buffer.write(byte as u16),
+            None => {
+                regs.interrupt_enable.clear_bits(UCTXIE_MASK);
+
+                // The CTS input may have been driven low since it was checked, in which case its
+                // interrupt already tried to resume the transmission.
+
+                if !is_pin_high(state.cts.load(Ordering::Relaxed)) {
+                    resume_transmission::<Instance>();
+                }
+            }
+        }
+    }
+}
+
+//
+// For sealed traits.
+//
+
+mod private {
+    pub trait Sealed {}
+
+    pub trait RtsPinAccess: Sealed {
+        /// Gets the location of the pin.
+        ///
+        /// # Returns
+        /// The location of the pin, packed by `pack_location`, or `NO_PIN`.
+        fn get_location(&self) -> u16;
+    }
+
+    pub trait CtsPinAccess: Sealed {
+        /// Gets the location of the pin.
+        ///
+        /// # Returns
+        /// The location of the pin, packed by `pack_location`, or `NO_PIN`.
+        fn get_location(&self) -> u16;
+
+        /// Enables the interrupt resuming the transmission when the pin is driven low.
+        ///
+        /// # Arguments
+        /// `handler` - Provides the function resuming the transmission.
+        fn enable_resume(&mut self, handler: fn());
+
+        /// Disables the interrupt resuming the transmission.
+        fn disable_resume(&mut self);
+    }
+}
+
+impl private::Sealed for NoFlowControl {}
+impl<Pin: PinX, OutputMode: GpioPushPullMode> private::Sealed
+    for GpioPin<Pin, GpioOut<OutputMode>>
+{
+}
+impl<Pin: PinWithInterrupt, InputMode: GpioInputMode> private::Sealed
+    for GpioPin<Pin, GpioIn<InputMode>>
+{
+}
+
+impl private::RtsPinAccess for NoFlowControl {
+    fn get_location(&self) -> u16 {
+        NO_PIN
+    }
+}
+
+impl private::CtsPinAccess for NoFlowControl {
+    fn get_location(&self) -> u16 {
+        NO_PIN
+    }
+
+    fn enable_resume(&mut self, _handler: fn()) {}
+
+    fn disable_resume(&mut self) {}
+}
+
+impl<Pin: PinX, OutputMode: GpioPushPullMode> private::RtsPinAccess
+    for GpioPin<Pin, GpioOut<OutputMode>>
+{
+    fn get_location(&self) -> u16 {
+        pack_location(self.get_pin())
+    }
+}
+
+impl<Pin: PinWithInterrupt, InputMode: GpioInputMode> private::CtsPinAccess
+    for GpioPin<Pin, GpioIn<InputMode>>
+{
+    fn get_location(&self) -> u16 {
+        pack_location(self.get_pin())
+    }
+
+    fn enable_resume(&mut self, handler: fn()) {
+        self.set_interrupt_handler(handler);
+        self.enable_interrupt(Edge::FallingEdge);
+    }
+
+    fn disable_resume(&mut self) {
+        self.disable_interrupt();
+        self.clear_interrupt_handler();
+    }
+}
@@ -0,0 +1,46 @@
+//! # Instance
+//! The `instance` module maps the eUSCI_A modules to the pins carrying their UART signals.
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{EusciA0, EusciA1, EusciA2, EusciA3, EusciAInstance},
+    pin::{Pin, PinX},
+};
+
+//
+// Traits
+//
+
+/// An eUSCI_A module operating in UART mode.
+pub trait UartInstance: EusciAInstance {
+    /// The pin carrying the transmitted data.
+    type TxPin: PinX;
+
+    /// The pin carrying the received data.
+    type RxPin: PinX;
+}
+
+//
+// Structures
+//
+
+macro_rules! impl_uart_instance {
+    ($($instance:ident, $tx:ty, $rx:ty;)+) => {
+        $(
+            impl UartInstance for $instance {
+                type TxPin = $tx;
+                type RxPin = $rx;
+            }
+        )+
+    };
+}
+
+impl_uart_instance!(
+    EusciA0, Pin<'A', 3>, Pin<'A', 2>;
+    EusciA1, Pin<'A', 11>, Pin<'A', 10>;
+    EusciA2, Pin<'B', 3>, Pin<'B', 2>;
+    EusciA3, Pin<'E', 7>, Pin<'E', 6>;
+);
@@ -0,0 +1,306 @@
+//! # IrDA
+//! The `irda` module includes structures and functions to utilize a UART with the IrDA SIR
+//! encoder and decoder of the eUSCI_A module, which turn each zero bit into a short light pulse.
+
+//
+// Dependencies
+//
+
+use crate::{
+    clock::ClockFrequencies,
+    eusci::{configure_eusci, get_eusci_a_registers},
+    uart::{Uart, UartBaudRate, UartError, UartInstance},
+};
+use core::fmt;
+
+//
+// Consts
+//
+
+const UCIREN_MASK: u16 = 1 << 0;
+const UCIRTXCLK_MASK: u16 = 1 << 1;
+const UCIRTXPL_SHIFT: u16 = 2;
+const UCIRRXFE_MASK: u16 = 1 << 8;
+const UCIRRXPL_MASK: u16 = 1 << 9;
+const UCIRRXFL_SHIFT: u16 = 10;
+
+/// The largest value of the pulse length and filter length fields.
+const MAX_FIELD_VALUE: u32 = 0x3F;
+
+/// The pulse length field value producing 3/16 of a bit time from the oversampling clock.
+const THREE_SIXTEENTHS_PULSE_LENGTH: u16 = 5;
+
+/// The number of half clock cycles added to the filter length field.
+const FILTER_LENGTH_OFFSET: u32 = 4;
+
+//
+// Structures
+//
+
+/// Represents the width of the transmitted pulses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrdaPulseWidth {
+    /// The pulses last 3/16 of a bit time, as specified by IrDA SIR.
+    ThreeSixteenths,
+
+    /// The pulses last a fixed time in nanoseconds, such as the 1.6 us minimum of IrDA SIR, which
+    /// saves power at low baud rates. The width is limited to 32 clock cycles.
+    FixedNs(u32),
+}
+
+/// Represents the level the transceiver drives on the receive pin while seeing a light pulse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrdaRxPolarity {
+    High,
+    Low,
+}
+
+/// Represents the configuration of the IrDA encoder and decoder.
+#[derive(Clone, Copy, Debug)]
+pub struct IrdaConfig {
+    /// The width of the transmitted pulses.
+    pub pulse_width: IrdaPulseWidth,
+
+    /// The level of the received pulses.
+    pub rx_polarity: IrdaRxPolarity,
+
+    /// The shortest received pulse, in nanoseconds, under which pulses are filtered out as
+    /// glitches. `None` disables the filter.
+    pub rx_filter_ns: Option<u32>,
+}
+
+impl Default for IrdaConfig {
+    /// Gets the configuration for 3/16 bit time pulses, received as high pulses without filter.
+    fn default() -> Self {
+        IrdaConfig {
+            pulse_width: IrdaPulseWidth::ThreeSixteenths,
+            rx_polarity: IrdaRxPolarity::High,
+            rx_filter_ns: None,
+        }
+    }
+}
+
+/// Represents a UART encoding and decoding its characters as IrDA SIR pulses.
+pub struct IrdaUart<Instance: UartInstance> {
+    /// The UART.
+    uart: Uart<Instance>,
+
+    /// The configuration of the encoder and decoder.
+    config: IrdaConfig,
+
+    /// The frequency of the clock feeding the module.
+    clock_hz: u32,
+}
+
+impl<Instance: UartInstance> IrdaUart<Instance> {
+    /// Creates a new IrDA UART, enabling the encoder and decoder.
+    ///
+    /// # Arguments
+    /// `uart` - Provides the UART.
+    /// `config` - Provides the pulse width, receive polarity and glitch filter.
+    /// `clocks` - Provides the clock frequencies.
+    ///
+    /// # Returns
+    /// A new IrDA UART.
+    pub fn new(mut uart: Uart<Instance>, config: &IrdaConfig, clocks: &ClockFrequencies) -> Self {
+        let clock_hz = uart.get_config().clock_source.get_frequency(clocks);
+
+        uart.flush();
+
+        let mut irda = IrdaUart {
+            uart,
+            config: *config,
+            clock_hz,
+        };
+        irda.apply_config();
+
+        irda
+    }
+
+    /// Sets the configuration of the encoder and decoder. Characters in flight are lost.
+    ///
+    /// # Arguments
+    /// `config` - Provides the pulse width, receive polarity and glitch filter.
+    pub fn set_irda_config(&mut self, config: &IrdaConfig) {
+        self.config = *config;
+        self.apply_config();
+    }
+
+    /// Gets the configuration of the encoder and decoder.
+    ///
+    /// # Returns
+    /// The configuration.
+    pub fn get_irda_config(&self) -> &IrdaConfig {
+        &self.config
+    }
+
+    /// Sets the baud rate. Characters in flight are lost.
+    ///
+    /// # Arguments
+    /// `baud_rate` - Provides the requested baud rate.
+    /// `clocks` - Provides the clock frequencies.
+    pub fn set_baud_rate(&mut self, baud_rate: u32, clocks: &ClockFrequencies) {
+        self.uart.set_baud_rate(baud_rate, clocks);
+
+        // The pulse width depends on whether the new baud rate is oversampled.
+
+        self.apply_config();
+    }
+
+    /// Gets the baud rate generator settings, including the actual baud rate and its error.
+    ///
+    /// # Returns
+    /// The baud rate generator settings.
+    pub fn get_baud_rate(&self) -> &UartBaudRate {
+        self.uart.get_baud_rate()
+    }
+
+    /// Sends a character, waiting for room in the transmit buffer.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the character to send.
+    pub fn write_byte(&mut self, byte: u8) {
+        self.uart.write_byte(byte);
+    }
+
+    /// Sends characters.
+    ///
+    /// # Arguments
+    /// `data` - Provides the characters to send.
+    pub fn write(&mut self, data: &[u8]) {
+        self.uart.write(data);
+    }
+
+    /// Waits until the module is done transmitting and receiving.
+    pub fn flush(&mut self) {
+        self.uart.flush();
+    }
+
+    /// Receives a character if one is available.
+    ///
+    /// # Returns
+    /// `Some(Ok(byte))` if a character was received.
+    /// `Some(Err(error))` if a character was received with an error.
+    /// `None` if no character is available.
+    pub fn try_read_byte(&mut self) -> Option<Result<u8, UartError>> {
+        self.uart.try_read_byte()
+    }
+
+    /// Receives a character, waiting for one to be available.
+    ///
+    /// # Returns
+    /// `Ok(byte)` if a character was received.
+    /// `Err(error)` if a character was received with an error.
+    pub fn read_byte(&mut self) -> Result<u8, UartError> {
+        self.uart.read_byte()
+    }
+
+    /// Receives characters, waiting for the buffer to be filled. Stops at the first character
+    /// received with an error.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the characters.
+    ///
+    /// # Returns
+    /// `Ok(())` if the buffer was filled.
+    /// `Err(error)` if a character was received with an error.
+    pub fn read(&mut self, buffer: &mut [u8]) -> Result<(), UartError> {
+        self.uart.read(buffer)
+    }
+
+    /// Disables the encoder and decoder.
+    ///
+    /// # Returns
+    /// The UART.
+    pub fn extract_parts(mut self) -> Uart<Instance> {
+        self.uart.flush();
+        configure_eusci::<Instance, _>(|_| {
+            get_eusci_a_registers::<Instance>().irda_control.write(0);
+        });
+
+        self.uart
+    }
+
+    /// Writes the configuration to the IrDA control register.
+    fn apply_config(&mut self) {
+        let bits = self.get_control_bits();
+
+        configure_eusci::<Instance, _>(|_| {
+            get_eusci_a_registers::<Instance>().irda_control.write(bits);
+        });
+    }
+
+    /// Gets the IrDA control register bits for the configuration.
+    ///
+    /// # Returns
+    /// The IrDA control register value, with the encoder and decoder enabled.
+    fn get_control_bits(&self) -> u16 {
+        let mut bits = UCIREN_MASK;
+
+        // The oversampling clock runs at 16 times the baud rate, so 3/16 of a bit time lasts 6 of
+        // its half cycles. Without oversampling, the same width is produced from the clock.
+
+        let baud_rate = self.uart.get_baud_rate().get_actual_baud_rate();
+
+        match self.config.pulse_width {
+            IrdaPulseWidth::ThreeSixteenths if self.uart.get_baud_rate().is_oversampling() => {
+                bits |= UCIRTXCLK_MASK | (THREE_SIXTEENTHS_PULSE_LENGTH << UCIRTXPL_SHIFT);
+            }
+            IrdaPulseWidth::ThreeSixteenths => {
+                let half_cycles =
+                    (self.clock_hz as u64 * 6 / (16 * baud_rate.max(1) as u64)) as u32;
+
+                bits |= get_field_value(half_cycles, 1) << UCIRTXPL_SHIFT;
+            }
+            IrdaPulseWidth::FixedNs(width_ns) => {
+                bits |= get_field_value(self.get_half_cycles(width_ns), 1) << UCIRTXPL_SHIFT;
+            }
+        }
+
+        if self.config.rx_polarity == IrdaRxPolarity::Low {
+            bits |= UCIRRXPL_MASK;
+        }
+
+        if let Some(filter_ns) = self.config.rx_filter_ns {
+            bits |= UCIRRXFE_MASK
+                | (get_field_value(self.get_half_cycles(filter_ns), FILTER_LENGTH_OFFSET)
+                    << UCIRRXFL_SHIFT);
+        }
+
+        bits
+    }
+
+    /// Converts a time to half cycles of the clock feeding the module.
+    ///
+    /// # Arguments
+    /// `time_ns` - Provides the time in nanoseconds.
+    ///
+    /// # Returns
+    /// The number of half clock cycles, rounded to the nearest.
+    fn get_half_cycles(&self, time_ns: u32) -> u32 {
+        ((time_ns as u64 * self.clock_hz as u64 * 2 + 500_000_000) / 1_000_000_000) as u32
+    }
+}
+
+impl<Instance: UartInstance> fmt::Write for IrdaUart<Instance> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+//
+// Module private functions.
+//
+
+/// Converts a number of half clock cycles to a pulse length or filter length field value.
+///
+/// # Arguments
+/// `half_cycles` - Provides the number of half clock cycles.
+/// `offset` - Provides the number of half clock cycles the field value is short of.
+///
+/// # Returns
+/// The field value, limited to the field width.
+fn get_field_value(half_cycles: u32, offset: u32) -> u16 {
+    half_cycles.saturating_sub(offset).min(MAX_FIELD_VALUE) as u16
+}
@@ -0,0 +1,606 @@
+//! # LIN
+//! The `lin` module includes structures and functions to utilize a UART on a Local Interconnect
+//! Network (LIN) bus, using the automatic baud rate detection of the eUSCI_A module to follow the
+//! baud rate of the master.
+//!
+//! The LIN transceiver is expected to echo the bus to the receive pin, as single-wire
+//! transceivers do, so every character sent is read back and compared.
+
+//
+// Dependencies
+//
+
+use crate::{
+    clock::ClockFrequencies,
+    eusci::{configure_eusci, get_eusci_a_registers, UCRXIFG_MASK, UCTXIFG_MASK},
+    uart::{
+        get_receive_error, get_register_baud_rate, DataBits, Parity, Uart, UartError, UartInstance,
+        UCBRKIE_MASK, UCBRK_MASK, UCMODE_MASK, UCMODE_SHIFT, UCTXBRK_MASK,
+    },
+};
+use embedded_hal::delay::DelayNs;
+
+//
+// Consts
+//
+
+/// The UCMODEx value selecting the UART mode with automatic baud rate detection.
+const AUTO_BAUD_RATE_MODE: u16 = 3;
+
+const UCABDEN_MASK: u16 = 1 << 0;
+const UCBTOE_MASK: u16 = 1 << 2;
+const UCSTOE_MASK: u16 = 1 << 3;
+
+/// The sync field sent after the break, whose edges are measured by the slaves.
+const SYNC_FIELD: u8 = 0x55;
+
+/// The largest frame identifier.
+const MAX_FRAME_ID: u8 = 0x3F;
+
+/// The first frame identifier reserved for diagnostic frames, which always use the classic
+/// checksum.
+const FIRST_DIAGNOSTIC_FRAME_ID: u8 = 0x3C;
+
+/// The largest number of data bytes in a frame.
+const MAX_FRAME_LENGTH: u8 = 8;
+
+/// The number of bit times in the nominal header, from the break to the protected identifier.
+const HEADER_BIT_TIMES: u32 = 34;
+
+/// The number of bit times in each character of the response.
+const CHARACTER_BIT_TIMES: u32 = 10;
+
+//
+// Structures
+//
+
+/// Represents a protected identifier, made of a frame identifier and two parity bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinPid(u8);
+
+impl LinPid {
+    /// Creates a protected identifier from a frame identifier, computing its parity bits.
+    ///
+    /// # Arguments
+    /// `id` - Provides the frame identifier, from 0 to 63.
+    ///
+    /// # Returns
+    /// A new protected identifier.
+    pub fn from_id(id: u8) -> Self {
+        debug_assert!(id <= MAX_FRAME_ID);
+
+        let id = id & MAX_FRAME_ID;
+        let bit = |offset: u8| (id >> offset) & 1;
+
+        let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+        let p1 = (bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) ^ 1;
+
+        LinPid(id | (p0 << 6) | (p1 << 7))
+    }
+
+    /// Parses a received protected identifier, checking its parity bits.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the received character.
+    ///
+    /// # Returns
+    /// `Some(pid)` if the parity bits match the frame identifier.
+    /// `None` otherwise.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        let pid = LinPid::from_id(byte & MAX_FRAME_ID);
+
+        if pid.0 == byte {
+            Some(pid)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the frame identifier.
+    ///
+    /// # Returns
+    /// The frame identifier, from 0 to 63.
+    pub fn get_id(&self) -> u8 {
+        self.0 & MAX_FRAME_ID
+    }
+
+    /// Gets the character carrying the protected identifier.
+    ///
+    /// # Returns
+    /// The frame identifier and parity bits.
+    pub fn get_byte(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Represents the checksum model of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinChecksumModel {
+    /// The checksum covers the data bytes only, as in LIN 1.x.
+    Classic,
+
+    /// The checksum covers the protected identifier and the data bytes, as in LIN 2.x. Diagnostic
+    /// frames still use the classic checksum.
+    Enhanced,
+}
+
+/// Represents which node sends the response of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinDirection {
+    /// This node sends the response.
+    Publish,
+
+    /// Another node sends the response, which this node receives.
+    Subscribe,
+}
+
+/// Represents an entry of a schedule table, describing a frame as seen by this node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinFrameSlot {
+    /// The frame identifier, from 0 to 63.
+    pub id: u8,
+
+    /// The number of data bytes, from 1 to 8.
+    pub length: u8,
+
+    /// Which node sends the response.
+    pub direction: LinDirection,
+
+    /// The checksum model.
+    pub checksum: LinChecksumModel,
+}
+
+/// Represents the errors detected on a LIN bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinError {
+    /// A character was received with an error.
+    Uart(UartError),
+
+    /// The break lasted longer than 22 bit times.
+    BreakTimeout,
+
+    /// The sync field lasted too long to be measured.
+    SyncTimeout,
+
+    /// The parity bits of the protected identifier did not match.
+    Parity,
+
+    /// The checksum of the response did not match.
+    Checksum,
+
+    /// A character read back from the bus differed from the one sent.
+    Readback,
+
+    /// The header or response was not complete in time.
+    Timeout,
+}
+
+impl From<UartError> for LinError {
+    fn from(error: UartError) -> Self {
+        LinError::Uart(error)
+    }
+}
+
+/// Represents a UART operating as a node of a LIN bus. The baud rate is measured on every sync
+/// field, so it follows the master.
+pub struct LinUart<Instance: UartInstance, Delay: DelayNs> {
+    /// The UART.
+    uart: Uart<Instance>,
+
+    /// The delay used to time out the header and response.
+    delay: Delay,
+
+    /// The frequency of the clock feeding the module.
+    clock_hz: u32,
+}
+
+impl<Instance: UartInstance, Delay: DelayNs> LinUart<Instance, Delay> {
+    /// Creates a new LIN node, enabling break and sync field detection.
+    ///
+    /// # Arguments
+    /// `uart` - Provides the UART, configured with the nominal baud rate of the bus, 8 data bits,
+    /// no parity and one stop bit.
+    /// `delay` - Provides the delay used to time out the header and response.
+    /// `clocks` - Provides the clock frequencies.
+    ///
+    /// # Returns
+    /// A new LIN node.
+    pub fn new(mut uart: Uart<Instance>, delay: Delay, clocks: &ClockFrequencies) -> Self {
+        let config = uart.get_config();
+
+        debug_assert!(config.data_bits == DataBits::Eight && config.parity == Parity::None);
+
+        let clock_hz = config.clock_source.get_frequency(clocks);
+
+        uart.flush();
+        configure_eusci::<Instance, _>(|regs| {
+            // Breaks are received as characters, so they are noticed while polling.
+
+            regs.control_0.modify(|value| {
+                (value & !UCMODE_MASK) | (AUTO_BAUD_RATE_MODE << UCMODE_SHIFT) | UCBRKIE_MASK
+            });
+            get_eusci_a_registers::<Instance>()
+                .auto_baud_control
+                .write(UCABDEN_MASK);
+        });
+
+        LinUart {
+            uart,
+            delay,
+            clock_hz,
+        }
+    }
+
+    /// Gets the baud rate measured on the last sync field, or the nominal one before any.
+    ///
+    /// # Returns
+    /// Baud rate in bits per second.
+    pub fn get_baud_rate(&self) -> u32 {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        get_register_baud_rate(
+            self.clock_hz,
+            regs.baud_rate.read(),
+            regs.modulation_control.read(),
+        )
+    }
+
+    /// Waits for a header on the bus, discarding any other characters.
+    ///
+    /// # Returns
+    /// `Ok(pid)` if a header was received.
+    /// `Err(error)` if the break, sync field or protected identifier was erroneous.
+    pub fn wait_header(&mut self) -> Result<LinPid, LinError> {
+        self.wait_break()?;
+
+        // The protected identifier follows the sync field, within the remaining header time.
+
+        let mut remaining_us = self.get_frame_time_us(CHARACTER_BIT_TIMES * 2);
+        let byte = self.read_byte_within(&mut remaining_us)?;
+
+        LinPid::from_byte(byte).ok_or(LinError::Parity)
+    }
+
+    /// Waits for a header of a frame in the schedule table, discarding the headers of other frames.
+    ///
+    /// # Arguments
+    /// `table` - Provides the frames handled by this node.
+    ///
+    /// # Returns
+    /// `Ok(slot)` if a header of a frame in the table was received.
+    /// `Err(error)` if the break, sync field or protected identifier was erroneous.
+    pub fn wait_frame<'a>(
+        &mut self,
+        table: &'a [LinFrameSlot],
+    ) -> Result<&'a LinFrameSlot, LinError> {
+        loop {
+            let pid = self.wait_header()?;
+
+            if let Some(slot) = table.iter().find(|slot| slot.id == pid.get_id()) {
+                return Ok(slot);
+            }
+        }
+    }
+
+    /// Sends the response of a frame, followed by its checksum.
+    ///
+    /// # Arguments
+    /// `slot` - Provides the frame.
+    /// `data` - Provides the data bytes, as many as the frame length.
+    ///
+    /// # Returns
+    /// `Ok(())` if the response was sent.
+    /// `Err(error)` if a character read back from the bus differed.
+    pub fn write_response(&mut self, slot: &LinFrameSlot, data: &[u8]) -> Result<(), LinError> {
+        debug_assert!(data.len() == slot.length as usize);
+
+        let checksum = calculate_lin_checksum(LinPid::from_id(slot.id), data, slot.checksum);
+        let mut remaining_us = self.get_response_time_us(slot.length);
+
+        for byte in data.iter().chain(core::iter::once(&checksum)) {
+            self.write_byte_checked(*byte, &mut remaining_us)?;
+        }
+
+        Ok(())
+    }
+
+    /// Receives the response of a frame, checking its checksum.
+    ///
+    /// # Arguments
+    /// `slot` - Provides the frame.
+    /// `buffer` - Receives the data bytes, as many as the frame length.
+    ///
+    /// # Returns
+    /// `Ok(())` if the response was received.
+    /// `Err(error)` if the response was erroneous or not complete in time.
+    pub fn read_response(
+        &mut self,
+        slot: &LinFrameSlot,
+        buffer: &mut [u8],
+    ) -> Result<(), LinError> {
+        debug_assert!(buffer.len() == slot.length as usize);
+
+        let mut remaining_us = self.get_response_time_us(slot.length);
+
+        for byte in buffer.iter_mut() {
+            *byte = self.read_byte_within(&mut remaining_us)?;
+        }
+
+        let checksum = self.read_byte_within(&mut remaining_us)?;
+
+        if checksum == calculate_lin_checksum(LinPid::from_id(slot.id), buffer, slot.checksum) {
+            Ok(())
+        } else {
+            Err(LinError::Checksum)
+        }
+    }
+
+    /// Sends a header, made of a break, a sync field and a protected identifier. Only the master
+    /// sends headers.
+    ///
+    /// # Arguments
+    /// `pid` - Provides the protected identifier.
+    ///
+    /// # Returns
+    /// `Ok(())` if the header was sent.
+    /// `Err(error)` if the header read back from the bus differed.
+    pub fn write_header(&mut self, pid: LinPid) -> Result<(), LinError> {
+        let regs = get_eusci_a_registers::<Instance>();
+        let mut remaining_us = self.get_frame_time_us(HEADER_BIT_TIMES);
+
+        // With automatic baud rate detection, sending the sync field with the break flag set sends
+        // both the break and the sync field, which are read back as a single break.
+
+        while (regs.interrupt_flag.read() & UCTXIFG_MASK) == 0 {}
+        regs.control_0.set_bits(UCTXBRK_MASK);
+        regs.transmit_buffer.write(SYNC_FIELD as u16);
+
+        self.wait_break_within(&mut remaining_us)?;
+        self.write_byte_checked(pid.get_byte(), &mut remaining_us)
+    }
+
+    /// Runs a frame of the schedule table as the master, sending its header, then sending or
+    /// receiving its response.
+    ///
+    /// # Arguments
+    /// `slot` - Provides the frame.
+    /// `data` - Provides the data bytes to send, or receives the data bytes received, as many as
+    /// the frame length.
+    ///
+    /// # Returns
+    /// `Ok(())` if the frame was complete.
+    /// `Err(error)` if the frame was erroneous or not complete in time.
+    pub fn run_frame(&mut self, slot: &LinFrameSlot, data: &mut [u8]) -> Result<(), LinError> {
+        self.write_header(LinPid::from_id(slot.id))?;
+
+        match slot.direction {
+            LinDirection::Publish => self.write_response(slot, data),
+            LinDirection::Subscribe => self.read_response(slot, data),
+        }
+    }
+
+    /// Disables break and sync field detection and restores the nominal baud rate.
+    ///
+    /// # Returns
+    /// The UART and the delay.
+    pub fn extract_parts(mut self) -> (Uart<Instance>, Delay) {
+        self.uart.flush();
+
+        let baud_rate = *self.uart.get_baud_rate();
+
+        configure_eusci::<Instance, _>(|regs| {
+            let regs_a = get_eusci_a_registers::<Instance>();
+
+            regs.control_0
+                .modify(|value| value & !(UCMODE_MASK | UCBRKIE_MASK));
+            regs_a.auto_baud_control.write(0);
+            regs_a.baud_rate.write(baud_rate.get_prescaler());
+            regs_a
+                .modulation_control
+                .write(baud_rate.get_modulation_bits());
+        });
+
+        (self.uart, self.delay)
+    }
+
+    /// Waits for a break and sync field, discarding any other characters.
+    ///
+    /// # Returns
+    /// `Ok(())` if a break and sync field were received.
+    /// `Err(error)` if the break or sync field lasted too long.
+    fn wait_break(&mut self) -> Result<(), LinError> {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        loop {
+            self.check_auto_baud_errors()?;
+
+            if (regs.interrupt_flag.read() & UCRXIFG_MASK) != 0 {
+                let status = regs.status.read();
+                let _ = regs.receive_buffer.read();
+
+                if (status & UCBRK_MASK) != 0 {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Waits for the break and sync field being sent to be read back.
+    ///
+    /// # Arguments
+    /// `remaining_us` - Provides and updates the time left for the header.
+    ///
+    /// # Returns
+    /// `Ok(())` if the break and sync field were read back.
+    /// `Err(error)` if something else was read back, or nothing in time.
+    fn wait_break_within(&mut self, remaining_us: &mut u32) -> Result<(), LinError> {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        loop {
+            self.check_auto_baud_errors()?;
+
+            if (regs.interrupt_flag.read() & UCRXIFG_MASK) != 0 {
+                let status = regs.status.read();
+                let _ = regs.receive_buffer.read();
+
+                return if (status & UCBRK_MASK) != 0 {
+                    Ok(())
+                } else {
+                    Err(LinError::Readback)
+                };
+            }
+
+            self.wait_tick(remaining_us)?;
+        }
+    }
+
+    /// Sends a character and reads it back from the bus.
+    ///
+    /// # Arguments
+    /// `byte` - Provides the character to send.
+    /// `remaining_us` - Provides and updates the time left for the header or response.
+    ///
+    /// # Returns
+    /// `Ok(())` if the character was read back.
+    /// `Err(error)` if a different character was read back, or nothing in time.
+    fn write_byte_checked(&mut self, byte: u8, remaining_us: &mut u32) -> Result<(), LinError> {
+        self.uart.write_byte(byte);
+
+        if self.read_byte_within(remaining_us)? == byte {
+            Ok(())
+        } else {
+            Err(LinError::Readback)
+        }
+    }
+
+    /// Receives a character, waiting for one to be available.
+    ///
+    /// # Arguments
+    /// `remaining_us` - Provides and updates the time left for the header or response.
+    ///
+    /// # Returns
+    /// `Ok(byte)` if a character was received.
+    /// `Err(error)` if the character was erroneous, or none was received in time.
+    fn read_byte_within(&mut self, remaining_us: &mut u32) -> Result<u8, LinError> {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        loop {
+            if (regs.interrupt_flag.read() & UCRXIFG_MASK) != 0 {
+                let status = regs.status.read();
+                let byte = regs.receive_buffer.read() as u8;
+
+                // A break in the middle of a frame aborts it.
+
+                if (status & UCBRK_MASK) != 0 {
+                    return Err(LinError::Uart(UartError::Framing));
+                }
+
+                return match get_receive_error(status) {
+                    Some(error) => Err(error.into()),
+                    None => Ok(byte),
+                };
+            }
+
+            self.wait_tick(remaining_us)?;
+        }
+    }
+
+    /// Waits for a microsecond, unless the time is up.
+    ///
+    /// # Arguments
+    /// `remaining_us` - Provides and updates the time left.
+    ///
+    /// # Returns
+    /// `Ok(())` if time was left.
+    /// `Err(LinError::Timeout)` otherwise.
+    fn wait_tick(&mut self, remaining_us: &mut u32) -> Result<(), LinError> {
+        if *remaining_us == 0 {
+            return Err(LinError::Timeout);
+        }
+
+        self.delay.delay_us(1);
+        *remaining_us -= 1;
+
+        Ok(())
+    }
+
+    /// Checks for, and clears, the errors of the break and sync field detection.
+    ///
+    /// # Returns
+    /// `Ok(())` if there were none.
+    /// `Err(error)` otherwise.
+    fn check_auto_baud_errors(&mut self) -> Result<(), LinError> {
+        let regs = get_eusci_a_registers::<Instance>();
+        let control = regs.auto_baud_control.read();
+
+        if (control & (UCBTOE_MASK | UCSTOE_MASK)) == 0 {
+            return Ok(());
+        }
+
+        regs.auto_baud_control.clear_bits(UCBTOE_MASK | UCSTOE_MASK);
+
+        if (control & UCBTOE_MASK) != 0 {
+            Err(LinError::BreakTimeout)
+        } else {
+            Err(LinError::SyncTimeout)
+        }
+    }
+
+    /// Gets the longest time allowed for a response, including its checksum.
+    ///
+    /// # Arguments
+    /// `length` - Provides the number of data bytes.
+    ///
+    /// # Returns
+    /// Time in microseconds.
+    fn get_response_time_us(&self, length: u8) -> u32 {
+        debug_assert!((1..=MAX_FRAME_LENGTH).contains(&length));
+
+        self.get_frame_time_us(CHARACTER_BIT_TIMES * (length as u32 + 1))
+    }
+
+    /// Gets the longest time allowed for a part of a frame, which is 40% longer than nominal.
+    ///
+    /// # Arguments
+    /// `bit_times` - Provides the nominal length of the part.
+    ///
+    /// # Returns
+    /// Time in microseconds.
+    fn get_frame_time_us(&self, bit_times: u32) -> u32 {
+        let bit_rate = self.get_baud_rate().max(1) as u64;
+
+        (bit_times as u64 * 14 * 1_000_000 / (10 * bit_rate)) as u32
+    }
+}
+
+//
+// Public functions.
+//
+
+/// Computes the checksum of a frame, which is the inverted sum with carry of the bytes it covers.
+///
+/// # Arguments
+/// `pid` - Provides the protected identifier of the frame.
+/// `data` - Provides the data bytes.
+/// `model` - Provides the checksum model.
+///
+/// # Returns
+/// The checksum.
+pub fn calculate_lin_checksum(pid: LinPid, data: &[u8], model: LinChecksumModel) -> u8 {
+    let mut sum: u16 = match model {
+        LinChecksumModel::Enhanced if pid.get_id() < FIRST_DIAGNOSTIC_FRAME_ID => {
+            pid.get_byte() as u16
+        }
+        _ => 0,
+    };
+
+    for byte in data {
+        sum += *byte as u16;
+
+        if sum > 0xFF {
+            sum -= 0xFF;
+        }
+    }
+
+    !(sum as u8)
+}
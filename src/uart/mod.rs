@@ -0,0 +1,205 @@
+//! # UART
+//! The `uart` module includes structures and functions to utilize the eUSCI_A modules as
+//! Universal Asynchronous Receiver/Transmitters (UART).
+
+//
+// Internal Modules
+//
+
+mod baudrate;
+mod blocking;
+mod buffered;
+mod instance;
+mod irda;
+mod lin;
+mod rs485;
+
+//
+// Reexports
+//
+
+pub use baudrate::*;
+pub use blocking::*;
+pub use buffered::*;
+pub use instance::*;
+pub use irda::*;
+pub use lin::*;
+pub use rs485::*;
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{EusciClockSource, UCOE_MASK},
+    spi::BitOrder,
+};
+
+//
+// Consts
+//
+
+pub(crate) const UCPEN_MASK: u16 = 1 << 15;
+pub(crate) const UCPAR_MASK: u16 = 1 << 14;
+pub(crate) const UCMSB_MASK: u16 = 1 << 13;
+pub(crate) const UC7BIT_MASK: u16 = 1 << 12;
+pub(crate) const UCSPB_MASK: u16 = 1 << 11;
+pub(crate) const UCMODE_SHIFT: u16 = 9;
+pub(crate) const UCMODE_MASK: u16 = 0x3 << UCMODE_SHIFT;
+pub(crate) const UCRXEIE_MASK: u16 = 1 << 5;
+pub(crate) const UCBRKIE_MASK: u16 = 1 << 4;
+pub(crate) const UCDORM_MASK: u16 = 1 << 3;
+pub(crate) const UCTXADDR_MASK: u16 = 1 << 2;
+pub(crate) const UCTXBRK_MASK: u16 = 1 << 1;
+
+pub(crate) const UCFE_MASK: u16 = 1 << 6;
+pub(crate) const UCPE_MASK: u16 = 1 << 4;
+pub(crate) const UCBRK_MASK: u16 = 1 << 3;
+pub(crate) const UCRXERR_MASK: u16 = 1 << 2;
+pub(crate) const UCADDR_MASK: u16 = 1 << 1;
+
+pub(crate) const UCTXCPTIFG_MASK: u16 = 1 << 3;
+
+//
+// Structures
+//
+
+/// Represents the number of data bits in a UART character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataBits {
+    Seven,
+    Eight,
+}
+
+/// Represents the parity bit of a UART character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+/// Represents the number of stop bits of a UART character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopBits {
+    One,
+    Two,
+}
+
+/// Represents the multiprocessor format, which marks the characters carrying an address on a
+/// multi-drop bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiprocessorMode {
+    /// Characters are not marked.
+    Disabled = 0,
+
+    /// Addresses follow an idle line of at least 10 bits.
+    IdleLine = 1,
+
+    /// Addresses carry an extra address bit set, after the data bits.
+    AddressBit = 2,
+}
+
+/// Represents the configuration of a UART.
+#[derive(Clone, Copy, Debug)]
+pub struct UartConfig {
+    /// The requested baud rate, in bits per second.
+    pub baud_rate: u32,
+
+    /// The number of data bits.
+    pub data_bits: DataBits,
+
+    /// The parity bit.
+    pub parity: Parity,
+
+    /// The number of stop bits.
+    pub stop_bits: StopBits,
+
+    /// The order in which the data bits are shifted.
+    pub bit_order: BitOrder,
+
+    /// The clock feeding the baud rate generator.
+    pub clock_source: EusciClockSource,
+}
+
+impl Default for UartConfig {
+    /// Gets the configuration for 115200 baud, 8 data bits, no parity and one stop bit, shifted
+    /// LSB first and clocked by SMCLK.
+    fn default() -> Self {
+        UartConfig {
+            baud_rate: 115_200,
+            data_bits: DataBits::Eight,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            bit_order: BitOrder::LsbFirst,
+            clock_source: EusciClockSource::Smclk,
+        }
+    }
+}
+
+impl UartConfig {
+    /// Gets the control register bits selecting the character format and clock.
+    ///
+    /// # Returns
+    /// The UCPEN, UCPAR, UCMSB, UC7BIT, UCSPB and UCSSEL bits, in place.
+    pub(crate) fn get_control_bits(&self) -> u16 {
+        let mut bits = self.clock_source.get_select_bits();
+
+        match self.parity {
+            Parity::None => (),
+            Parity::Even => bits |= UCPEN_MASK | UCPAR_MASK,
+            Parity::Odd => bits |= UCPEN_MASK,
+        }
+
+        if self.bit_order == BitOrder::MsbFirst {
+            bits |= UCMSB_MASK;
+        }
+
+        if self.data_bits == DataBits::Seven {
+            bits |= UC7BIT_MASK;
+        }
+
+        if self.stop_bits == StopBits::Two {
+            bits |= UCSPB_MASK;
+        }
+
+        bits
+    }
+}
+
+/// Represents the errors detected on a received UART character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UartError {
+    /// The stop bit was low.
+    Framing,
+
+    /// The parity bit did not match the data.
+    Parity,
+
+    /// A character was lost because the previous one was not read in time.
+    Overrun,
+}
+
+//
+// Crate functions
+//
+
+/// Gets the error of a received character from the status register.
+///
+/// # Arguments
+/// `status` - Provides the status register, read before the receive buffer.
+///
+/// # Returns
+/// `Some(error)` if the character was received with an error.
+/// `None` otherwise.
+pub(crate) fn get_receive_error(status: u16) -> Option<UartError> {
+    if (status & UCOE_MASK) != 0 {
+        Some(UartError::Overrun)
+    } else if (status & UCFE_MASK) != 0 {
+        Some(UartError::Framing)
+    } else if (status & UCPE_MASK) != 0 {
+        Some(UartError::Parity)
+    } else {
+        None
+    }
+}
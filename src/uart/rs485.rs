@@ -0,0 +1,152 @@
+//! # RS-485
+//! The `rs485` module includes structures and functions to utilize a UART on a half-duplex RS-485
+//! bus, whose transceiver only drives the bus while its driver enable (DE) input is high.
+
+//
+// Dependencies
+//
+
+use crate::{
+    eusci::{get_eusci_a_registers, UCTXIFG_MASK},
+    gpio::GpioPinOutput,
+    interrupt::single_proc_critical_section,
+    uart::{Uart, UartError, UartInstance, UCTXCPTIFG_MASK},
+};
+
+//
+// Structures
+//
+
+/// Represents a UART driving a half-duplex RS-485 transceiver. The driver is only enabled while
+/// characters are being sent, so the bus is released for the other nodes in between.
+pub struct Rs485Uart<Instance: UartInstance, De: GpioPinOutput> {
+    /// The UART.
+    uart: Uart<Instance>,
+
+    /// The driver enable output.
+    de: De,
+}
+
+impl<Instance: UartInstance, De: GpioPinOutput> Rs485Uart<Instance, De> {
+    /// Creates a new RS-485 UART. The driver is disabled.
+    ///
+    /// # Arguments
+    /// `uart` - Provides the UART.
+    /// `de` - Provides the driver enable output pin.
+    ///
+    /// # Returns
+    /// A new RS-485 UART.
+    pub fn new(mut uart: Uart<Instance>, mut de: De) -> Self {
+        uart.flush();
+        de.clear();
+
+        Rs485Uart { uart, de }
+    }
+
+    /// Sends characters. The driver is enabled before the first character, and disabled once the
+    /// stop bit of the last character is sent.
+    ///
+    /// # Arguments
+    /// `data` - Provides the characters to send.
+    pub fn write(&mut self, data: &[u8]) {
+        let Some((last, data)) = data.split_last() else {
+            return;
+        };
+
+        self.de.set();
+        self.uart.write(data);
+        self.send_last_and_release(|uart| uart.write_byte(*last));
+    }
+
+    /// Sends an address in the multiprocessor format, followed by characters. The driver is kept
+    /// enabled for the whole frame.
+    ///
+    /// # Arguments
+    /// `address` - Provides the address to send.
+    /// `data` - Provides the characters to send after the address.
+    pub fn write_addressed(&mut self, address: u8, data: &[u8]) {
+        self.de.set();
+
+        match data.split_last() {
+            Some((last, data)) => {
+                self.uart.write_address(address);
+                self.uart.write(data);
+                self.send_last_and_release(|uart| uart.write_byte(*last));
+            }
+            None => self.send_last_and_release(|uart| uart.write_address(address)),
+        }
+    }
+
+    /// Sets whether the receiver sleeps until an address is received.
+    ///
+    /// # Arguments
+    /// `dormant` - Provides whether only addresses are received.
+    pub fn set_dormant(&mut self, dormant: bool) {
+        self.uart.set_dormant(dormant);
+    }
+
+    /// Receives a character if one is available.
+    ///
+    /// # Returns
+    /// `Some(Ok(byte))` if a character was received.
+    /// `Some(Err(error))` if a character was received with an error.
+    /// `None` if no character is available.
+    pub fn try_read_byte(&mut self) -> Option<Result<u8, UartError>> {
+        self.uart.try_read_byte()
+    }
+
+    /// Receives characters, waiting for the buffer to be filled.
+    ///
+    /// # Arguments
+    /// `buffer` - Receives the characters.
+    ///
+    /// # Returns
+    /// `Ok(())` if the buffer was filled.
+    /// `Err(error)` if a character was received with an error.
+    pub fn read(&mut self, buffer: &mut [u8]) -> Result<(), UartError> {
+        self.uart.read(buffer)
+    }
+
+    /// Receives a character in the multiprocessor format, waiting for one to be available.
+    ///
+    /// # Returns
+    /// `Ok((byte, true))` if an address was received.
+    /// `Ok((byte, false))` if data was received.
+    /// `Err(error)` if a character was received with an error.
+    pub fn read_addressed_byte(&mut self) -> Result<(u8, bool), UartError> {
+        self.uart.read_addressed_byte()
+    }
+
+    /// Extracts the UART and driver enable pin. The driver is left disabled.
+    ///
+    /// # Returns
+    /// The UART and the driver enable output pin.
+    pub fn extract_parts(self) -> (Uart<Instance>, De) {
+        (self.uart, self.de)
+    }
+
+    /// Sends the last character of a frame, waits for its stop bit to be sent, then disables the
+    /// driver.
+    ///
+    /// # Arguments
+    /// `send_func` - A function sending the last character.
+    fn send_last_and_release<F: FnMut(&mut Uart<Instance>)>(&mut self, mut send_func: F) {
+        let regs = get_eusci_a_registers::<Instance>();
+
+        // The transmit complete flag is set whenever the shift register empties with nothing
+        // left in the transmit buffer, so it is cleared right before the last character is loaded
+        // and can then only be set by that character. Both are done in a critical section, so an
+        // interrupt service routine cannot delay the load until the previous character is sent.
+        // The transmit buffer is empty by then, so loading the character does not block.
+
+        while (regs.interrupt_flag.read() & UCTXIFG_MASK) == 0 {}
+        single_proc_critical_section(|_| {
+            regs.interrupt_flag.clear_bits(UCTXCPTIFG_MASK);
+            send_func(&mut self.uart);
+        });
+
+        while (regs.interrupt_flag.read() & UCTXCPTIFG_MASK) == 0 {}
+
+        self.de.clear();
+    }
+}